@@ -0,0 +1,195 @@
+use crate::codegen::emitter::emit_object;
+use crate::codegen::layout::layout;
+use crate::diagnostics::diagnostic::{Diagnostic, Severity};
+use crate::diagnostics::pragma::{parse_pragmas, Suppressions};
+use crate::lexer::includes::tokenise_with_includes;
+use crate::lexer::token::Token;
+use crate::object::object_file::ObjectFile;
+use crate::parser::cst::CstNode;
+use crate::parser::Parser;
+use crate::sema::ast::file::File;
+use crate::sema::constant_expander::expand_constants;
+use crate::sema::entry_checker::check_entry_points;
+use crate::sema::expression_evaluator::evaluate_expressions;
+use crate::sema::immediate_checker::check_immediates;
+use crate::sema::label_checker::check_labels;
+use crate::sema::label_expander::expand_labels;
+use crate::sema::macro_expander::expand_macros;
+use crate::sema::operand_checker::check_operands;
+use crate::sema::reachability_checker::check_reachability;
+use crate::sema::sema_error::SemaError;
+use crate::sema::sema_warning::{SemaWarning, WarningKind};
+use crate::sema::size_checker::check_sizes;
+use crate::sema::symbol_table::build_symbol_table;
+use crate::source_map::SourceMap;
+
+
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum Stage {
+    Tokens,
+    Cst,
+    Ast,
+    Object
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub stop_after: Stage,
+    pub enabled_warnings: Vec<WarningKind>,
+    pub warnings_as_errors: Vec<WarningKind>,
+    pub include_paths: Vec<String>,
+    pub max_errors: usize
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { stop_after: Stage::Object, enabled_warnings: WarningKind::defaults(), warnings_as_errors: Vec::new(), include_paths: Vec::new(), max_errors: 0 }
+    }
+}
+
+#[derive(Debug)]
+pub enum Artifact {
+    Tokens(Vec<Token>),
+    Cst(CstNode),
+    Ast(File),
+    Object(ObjectFile)
+}
+
+#[derive(Debug)]
+#[derive(Default)]
+pub struct Diagnostics {
+    pub errors: Vec<Diagnostic>,
+    pub warnings: Vec<Diagnostic>,
+    pub sources: SourceMap
+}
+
+#[derive(Debug)]
+pub struct Output {
+    pub artifact: Artifact,
+    pub warnings: Vec<Diagnostic>,
+    pub sources: SourceMap
+}
+
+
+pub fn assemble(name: &str, source: &str, options: &Options) -> Result<Output, Diagnostics> {
+    let mut diagnostics = Diagnostics::default();
+
+    let artifact = match run_stages(name, source, options, &mut diagnostics) {
+        Some(artifact) if diagnostics.errors.is_empty() => artifact,
+        _ => return Err(diagnostics)
+    };
+
+    Ok(Output { artifact, warnings: diagnostics.warnings, sources: diagnostics.sources })
+}
+
+
+fn run_stages(name: &str, source: &str, options: &Options, diagnostics: &mut Diagnostics) -> Option<Artifact> {
+    let main = diagnostics.sources.add(name, source.to_string(), None);
+
+    let (tokens, errors, truncated) = tokenise_with_includes(main, &mut diagnostics.sources, &options.include_paths, options.max_errors);
+
+    let mut suppressions = Suppressions::default();
+    for (file, source_file) in diagnostics.sources.files.iter().enumerate() {
+        diagnostics.errors.extend(parse_pragmas(&source_file.source, file, &mut suppressions));
+    }
+
+    if !errors.is_empty() {
+        diagnostics.errors.extend(errors.iter().map(|e| e.diagnostic()));
+
+        if truncated {
+            diagnostics.errors.push(Diagnostic::error("E0002", format!("Aborting after {} errors.", options.max_errors).as_str(), None).with_help("Raise the limit with --max-errors, or pass --max-errors 0 to report every error."));
+        }
+        return None;
+    }
+
+    if options.stop_after == Stage::Tokens {
+        return Some(Artifact::Tokens(tokens));
+    }
+
+    let (cst, errors) = Parser::parse(&tokens);
+    diagnostics.errors.extend(errors.iter().map(|e| e.diagnostic()));
+
+    if options.stop_after == Stage::Cst {
+        return Some(Artifact::Cst(cst));
+    }
+
+    let mut file = File::from(&cst);
+
+    if let Err(e) = expand_labels(&mut file) {
+        diagnostics.errors.push(e.diagnostic());
+        return None;
+    }
+
+    check(build_symbol_table(&file), diagnostics)?;
+    check(check_entry_points(&file), diagnostics)?;
+    check(expand_constants(&mut file), diagnostics)?;
+    check(evaluate_expressions(&mut file), diagnostics)?;
+    check(expand_macros(&mut file), diagnostics)?;
+    check(check_operands(&file), diagnostics)?;
+    check(check_sizes(&file), diagnostics)?;
+
+    let mut warnings = Vec::new();
+    let immediates = check_immediates(&file, &mut warnings);
+    check_labels(&file, &mut warnings);
+    check_reachability(&file, &mut warnings);
+    report_warnings(warnings, options, &suppressions, diagnostics);
+
+    check(immediates, diagnostics)?;
+
+    if !diagnostics.errors.is_empty() {
+        return None;
+    }
+
+    if options.stop_after == Stage::Ast {
+        return Some(Artifact::Ast(file));
+    }
+
+    let layout = match layout(&file) {
+        Ok(layout) => layout,
+        Err(e) => {
+            diagnostics.errors.push(e.diagnostic());
+            return None;
+        }
+    };
+
+    match emit_object(&file, &layout) {
+        Ok(object) => Some(Artifact::Object(object)),
+        Err(e) => {
+            diagnostics.errors.push(e.diagnostic());
+            None
+        }
+    }
+}
+
+
+fn report_warnings(warnings: Vec<SemaWarning>, options: &Options, suppressions: &Suppressions, diagnostics: &mut Diagnostics) {
+    for w in warnings {
+        if !options.enabled_warnings.contains(&w.kind()) || suppressions.allows(w.kind(), w.span()) {
+            continue;
+        }
+
+        let mut diagnostic = w.diagnostic();
+
+        if options.warnings_as_errors.contains(&w.kind()) {
+            diagnostic.severity = Severity::Error;
+            diagnostic.notes.push(format!("-Werror turned the {} warning into an error.", w.kind().name()));
+            diagnostics.errors.push(diagnostic);
+        } else {
+            diagnostics.warnings.push(diagnostic);
+        }
+    }
+}
+
+
+fn check<T>(result: Result<T, Vec<SemaError>>, diagnostics: &mut Diagnostics) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(errors) => {
+            diagnostics.errors.extend(errors.iter().map(|e| e.diagnostic()));
+            None
+        }
+    }
+}
@@ -0,0 +1,196 @@
+
+use asmc_rust::sema::sema_warning::WarningKind;
+
+
+pub static USAGE: &str = "\
+Usage: asmc [options] <input>...
+
+Inputs are assembly sources or object files (object files are only accepted with --emit bin).
+
+Options:
+  -o <path>                      Output path, - for stdout
+  --emit tokens|cst|ast|obj|bin  Stop after the given stage (default: bin)
+  --format raw|ihex              Image format for --emit bin (default: raw)
+  -I <dir>                       Add an include search path
+  -w                             Disable all warnings
+  -W<warning>, -Wno-<warning>    Enable or disable a warning
+  -Wall                          Enable all warnings
+  -Werror                        Treat warnings as errors
+  -Werror=<warning>              Enable a warning and treat it as an error
+  --color auto|always|never      Colour diagnostics (default: auto)
+  --diagnostic-format text|json  Print diagnostics as text or as one JSON object per line (default: text)
+  --max-errors <n>               Stop lexing after n errors, 0 for no limit (default: 20)
+  -h, --help                     Print this help
+
+Warnings: unused-label (off by default), shadowed-label, truncated-immediate, unreachable-code, raw-entry-point.
+Suppress them in source with ;@allow <warning>... (this or the next line) or ;@allow-file <warning>...";
+
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum Emit {
+    Tokens,
+    Cst,
+    Ast,
+    Obj,
+    Bin
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum Format {
+    Raw,
+    IntelHex
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum Color {
+    Auto,
+    Always,
+    Never
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum DiagnosticFormat {
+    Text,
+    Json
+}
+
+#[derive(Debug)]
+pub struct Options {
+    pub inputs: Vec<String>,
+    pub output: Option<String>,
+    pub emit: Emit,
+    pub format: Format,
+    pub include_paths: Vec<String>,
+    pub no_warnings: bool,
+    pub enabled_warnings: Vec<WarningKind>,
+    pub warnings_as_errors: bool,
+    pub error_warnings: Vec<WarningKind>,
+    pub color: Color,
+    pub diagnostic_format: DiagnosticFormat,
+    pub max_errors: usize,
+    pub help: bool
+}
+
+
+pub fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        inputs: Vec::new(),
+        output: None,
+        emit: Emit::Bin,
+        format: Format::Raw,
+        include_paths: Vec::new(),
+        no_warnings: false,
+        enabled_warnings: WarningKind::defaults(),
+        warnings_as_errors: false,
+        error_warnings: Vec::new(),
+        color: Color::Auto,
+        diagnostic_format: DiagnosticFormat::Text,
+        max_errors: 20,
+        help: false
+    };
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => options.help = true,
+            "-o" => options.output = Some(value(&mut args, "-o")?),
+            "--emit" => {
+                options.emit = match value(&mut args, "--emit")?.as_str() {
+                    "tokens" => Emit::Tokens,
+                    "cst" => Emit::Cst,
+                    "ast" => Emit::Ast,
+                    "obj" => Emit::Obj,
+                    "bin" => Emit::Bin,
+                    other => return Err(format!("Unknown --emit stage: {other}"))
+                }
+            },
+            "--format" => {
+                options.format = match value(&mut args, "--format")?.as_str() {
+                    "raw" => Format::Raw,
+                    "ihex" => Format::IntelHex,
+                    other => return Err(format!("Unknown --format: {other}"))
+                }
+            },
+            "--color" => {
+                options.color = match value(&mut args, "--color")?.as_str() {
+                    "auto" => Color::Auto,
+                    "always" => Color::Always,
+                    "never" => Color::Never,
+                    other => return Err(format!("Unknown --color: {other}"))
+                }
+            },
+            "--diagnostic-format" => {
+                options.diagnostic_format = match value(&mut args, "--diagnostic-format")?.as_str() {
+                    "text" => DiagnosticFormat::Text,
+                    "json" => DiagnosticFormat::Json,
+                    other => return Err(format!("Unknown --diagnostic-format: {other}"))
+                }
+            },
+            "--max-errors" => {
+                let count = value(&mut args, "--max-errors")?;
+                options.max_errors = count.parse().map_err(|_| format!("Invalid --max-errors count: {count}"))?;
+            },
+            "-I" => options.include_paths.push(value(&mut args, "-I")?),
+            "-w" => options.no_warnings = true,
+            "-Werror" => options.warnings_as_errors = true,
+            "-Wall" => options.enabled_warnings = WarningKind::ALL.to_vec(),
+            _ if arg.starts_with("-Werror=") => {
+                let kind = warning_kind(&arg["-Werror=".len()..])?;
+                enable(&mut options.enabled_warnings, kind);
+                enable(&mut options.error_warnings, kind);
+            },
+            _ if arg.starts_with("-Wno-") => {
+                let kind = warning_kind(&arg["-Wno-".len()..])?;
+                options.enabled_warnings.retain(|enabled| *enabled != kind);
+                options.error_warnings.retain(|enabled| *enabled != kind);
+            },
+            _ if arg.starts_with("-W") => enable(&mut options.enabled_warnings, warning_kind(&arg["-W".len()..])?),
+            _ if arg.starts_with("-I") => options.include_paths.push(arg[2..].to_string()),
+            _ if arg.starts_with('-') && arg.len() > 1 => return Err(format!("Unknown option: {arg}")),
+            _ => options.inputs.push(arg.clone())
+        }
+    }
+
+    if options.help {
+        return Ok(options);
+    }
+
+    if options.inputs.is_empty() {
+        return Err("No input files".to_string());
+    }
+
+    if options.output.is_some() && options.inputs.len() > 1 && options.emit != Emit::Bin {
+        return Err("-o with multiple inputs is only allowed with --emit bin".to_string());
+    }
+
+    Ok(options)
+}
+
+
+fn warning_kind(name: &str) -> Result<WarningKind, String> {
+    WarningKind::from_name(name).ok_or_else(|| format!("Unknown warning: {name}"))
+}
+
+
+fn enable(kinds: &mut Vec<WarningKind>, kind: WarningKind) {
+    if !kinds.contains(&kind) {
+        kinds.push(kind);
+    }
+}
+
+
+fn value<'a>(args: &mut impl Iterator<Item = &'a String>, option: &str) -> Result<String, String> {
+    match args.next() {
+        Some(value) => Ok(value.clone()),
+        None => Err(format!("Missing value after {option}"))
+    }
+}
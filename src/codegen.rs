@@ -0,0 +1,5 @@
+pub mod codegen_error;
+pub mod opcodes;
+pub mod encoder;
+pub mod layout;
+pub mod emitter;
@@ -0,0 +1,46 @@
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::sema::sema_error::SemaError;
+use crate::span::Span;
+
+
+pub struct CodegenError {
+    code: &'static str,
+    desc: String,
+    span: Span,
+    notes: Vec<String>,
+    help: Option<String>
+}
+
+
+impl CodegenError {
+    pub fn desc(&self) -> String {
+        format!("*** CODEGEN ERROR [LINE {}, COLUMN {}]: {}", self.span.line, self.span.column, self.desc)
+    }
+
+    pub fn new(desc: &str, span: Span) -> Self {
+        CodegenError { code: "E0401", desc: desc.to_string(), span, notes: Vec::new(), help: None }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn with_help(mut self, help: &str) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = Diagnostic::error(self.code, &self.desc, Some(self.span));
+        diagnostic.notes = self.notes.clone();
+        diagnostic.help = self.help.clone();
+        diagnostic
+    }
+}
+
+impl From<SemaError> for CodegenError {
+    fn from(error: SemaError) -> Self {
+        let diagnostic = error.diagnostic();
+        CodegenError { code: diagnostic.code, desc: diagnostic.message, span: error.span(), notes: diagnostic.notes, help: diagnostic.help }
+    }
+}
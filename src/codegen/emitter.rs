@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use crate::codegen::codegen_error::CodegenError;
+use crate::codegen::encoder::encode_instruction;
+use crate::codegen::layout::Layout;
+use crate::object::object_file::{EntryPoint, Export, Import, LineEntry, ObjectFile, Relocation, RelocationKind, RelocationTarget, Section};
+use crate::sema::ast::assignment::{Assignment, AssignmentValue};
+use crate::sema::ast::data_type::DataType;
+use crate::sema::ast::expression::Expression;
+use crate::sema::ast::file::File;
+use crate::sema::ast::labels::LabelAccess;
+use crate::sema::ast::res_directive::ResDirective;
+use crate::sema::ast::statement::Statement;
+use crate::sema::expression_evaluator::{evaluate, AddressPart, Base, Value};
+use crate::sema::immediate_checker::{field_range, reinterpret, DATA_FIELD};
+
+
+
+pub static TEXT_SECTION_NAME: &str = "text";
+
+
+pub fn emit_object(file: &File, layout: &Layout) -> Result<ObjectFile, CodegenError> {
+    let mut imports = Vec::new();
+    let mut import_indices: HashMap<String, u16> = HashMap::new();
+
+    for stmt in &file.statements {
+        if let Statement::ImportDirective(node) = stmt {
+            let name = node.label_intern.str.clone().unwrap();
+
+            import_indices.insert(name, imports.len() as u16);
+            imports.push(Import { name: node.label_extern.str.clone().unwrap() });
+        }
+    }
+
+    let resolve = |label: &LabelAccess| {
+        let name = label.str.as_ref().unwrap();
+
+        if import_indices.contains_key(name) {
+            return Some(Value::address(0, Base::Import(name.clone())));
+        }
+
+        layout.symbols.get(name).map(|address| Value::address(*address as i32, Base::Section))
+    };
+
+    let mut data = Vec::with_capacity(layout.size as usize);
+    let mut exports: Vec<Export> = Vec::new();
+    let mut relocations = Vec::new();
+    let mut line_table = Vec::new();
+    let mut entry = None;
+
+    for (stmt, address) in file.statements.iter().zip(&layout.addresses) {
+        match stmt {
+            Statement::Instruction(node) => {
+                let encoded = encode_instruction(node, &resolve)?;
+
+                for fixup in encoded.fixups {
+                    relocations.push(relocation(address + fixup.offset as u32, &fixup.value, &import_indices));
+                }
+
+                data.extend(encoded.bytes);
+                if node.span.file == 0 {
+                    line_table.push(LineEntry { section: 0, offset: *address, line: node.span.line });
+                }
+            },
+            Statement::ResDirective(node) => {
+                let values = res_values(node, &resolve)?;
+
+                for (i, value) in values.iter().enumerate() {
+                    if value.base.is_some() {
+                        relocations.push(relocation(address + i as u32, value, &import_indices));
+                    }
+                }
+
+                data.extend(values.iter().map(Value::byte));
+                if node.span.file == 0 {
+                    line_table.push(LineEntry { section: 0, offset: *address, line: node.span.line });
+                }
+            },
+            Statement::ExportDirective(node) => {
+                let name = node.label_intern.str.as_ref().unwrap();
+                let external_name = node.label_extern.str.clone().unwrap();
+
+                if import_indices.contains_key(name) {
+                    return Err(CodegenError::new(format!("Imported label {} cannot be exported.", name).as_str(), node.span));
+                }
+
+                if exports.iter().any(|export| export.name == external_name) {
+                    return Err(CodegenError::new(format!("External label {} exported more than once.", external_name).as_str(), node.span));
+                }
+
+                exports.push(Export { name: external_name, section: 0, offset: layout.symbols[name] });
+            },
+            Statement::StartDirective(_) => entry = Some(EntryPoint { section: 0, offset: *address }),
+            _ => {}
+        }
+    }
+
+    let sections = vec![Section { name: TEXT_SECTION_NAME.to_string(), data }];
+
+    Ok(ObjectFile { sections, exports, imports, relocations, entry, line_table: Some(line_table) })
+}
+
+
+fn relocation(offset: u32, value: &Value, import_indices: &HashMap<String, u16>) -> Relocation {
+    let kind = match value.part {
+        AddressPart::Whole => RelocationKind::Absolute16,
+        AddressPart::Low => RelocationKind::Low8,
+        AddressPart::High => RelocationKind::High8
+    };
+
+    let target = match value.base.as_ref().unwrap() {
+        Base::Section => RelocationTarget::Section(0),
+        Base::Import(name) => RelocationTarget::Import(import_indices[name])
+    };
+
+    Relocation { section: 0, offset, kind, target, addend: value.constant }
+}
+
+
+fn res_values(node: &ResDirective, resolve: &dyn Fn(&LabelAccess) -> Option<Value>) -> Result<Vec<Value>, CodegenError> {
+    let size = node.data_type.size() as usize;
+
+    let Some(assignment) = &node.assignment else {
+        return Ok(vec![Value::constant(0); size]);
+    };
+
+    match assignment_values(element_type(&node.data_type), size, assignment, resolve)? {
+        Some(mut values) if values.len() <= size => {
+            values.resize(size, Value::constant(0));
+            Ok(values)
+        },
+        _ => Err(CodegenError::new(format!("Initializer of {} does not fit into its {}-byte type.", node.label.str.as_ref().unwrap(), size).as_str(), node.span))
+    }
+}
+
+
+fn element_type(data_type: &DataType) -> Option<&DataType> {
+    match data_type {
+        DataType::Arr(_, element) => Some(element),
+        _ => None
+    }
+}
+
+
+fn assignment_values(element: Option<&DataType>, slot: usize, assignment: &Assignment, resolve: &dyn Fn(&LabelAccess) -> Option<Value>) -> Result<Option<Vec<Value>>, CodegenError> {
+    let mut once = Vec::new();
+
+    for value in &assignment.values {
+        match value {
+            AssignmentValue::Number(n) => once.push(data_value(n, resolve)?),
+            AssignmentValue::String(s) => once.extend(s.iter().map(|c| Value::constant(*c as i32))),
+            AssignmentValue::Assignment(inner) => match element {
+                Some(element) => {
+                    let element_size = element.size() as usize;
+                    let Some(mut element_values) = assignment_values(element_type(element), element_size, inner, resolve)? else {
+                        return Ok(None);
+                    };
+
+                    if element_values.len() > element_size {
+                        return Ok(None);
+                    }
+
+                    element_values.resize(element_size, Value::constant(0));
+                    once.extend(element_values);
+                },
+                None => match assignment_values(None, slot.saturating_sub(once.len()), inner, resolve)? {
+                    Some(values) => once.extend(values),
+                    None => return Ok(None)
+                }
+            }
+        }
+    }
+
+    let repetition = assignment.repetition.value().unwrap() as u32 as usize;
+
+    if repetition != 0 {
+        if once.len().saturating_mul(repetition) > slot {
+            return Ok(None);
+        }
+        return Ok(Some(vec![once; repetition].concat()));
+    }
+
+    if once.is_empty() {
+        return Ok(Some(once));
+    }
+
+    Ok(Some(once.iter().cloned().cycle().take(slot.max(once.len())).collect()))
+}
+
+
+fn data_value(expression: &Expression, resolve: &dyn Fn(&LabelAccess) -> Option<Value>) -> Result<Value, CodegenError> {
+    let Some(constant) = expression.value() else {
+        let value = evaluate(expression, resolve)?;
+
+        if value.base.is_some() && value.part == AddressPart::Whole {
+            return Err(CodegenError::new("A label address does not fit into one byte.", expression.span).with_help("Store it as two bytes with lo() and hi()."));
+        }
+
+        let (min, max) = field_range(&DATA_FIELD);
+        let constant = value.constant as i64;
+
+        if value.base.is_none() && (constant < min || constant > max) && reinterpret(constant, &DATA_FIELD).is_none() {
+            return Err(CodegenError::new(format!("Initializer value #{} does not fit in a byte (expected #{} to #{}).", value.constant, min, max).as_str(), expression.span));
+        }
+
+        return Ok(value);
+    };
+
+    Ok(Value::constant(constant))
+}
@@ -0,0 +1,335 @@
+use crate::codegen::codegen_error::CodegenError;
+use crate::codegen::opcodes::{condition_code, encoded_len, long_register_number, matching_form, opcode, port_number, register_number, system_register_number, ImmediateField, Operand};
+use crate::sema::ast::instruction::Instruction;
+use crate::sema::ast::instruction_arg::InstructionArg;
+use crate::sema::ast::labels::LabelAccess;
+use crate::sema::expression_evaluator::{evaluate, AddressPart, Value};
+use crate::sema::immediate_checker::field_range;
+use crate::span::Span;
+
+
+
+pub struct Fixup {
+    pub offset: usize,
+    pub value: Value
+}
+
+pub struct EncodedInstruction {
+    pub bytes: Vec<u8>,
+    pub fixups: Vec<Fixup>
+}
+
+
+pub fn encode_instruction(instruction: &Instruction, resolve: &dyn Fn(&LabelAccess) -> Option<Value>) -> Result<EncodedInstruction, CodegenError> {
+    let Some((form_index, form)) = matching_form(instruction) else {
+        return Err(CodegenError::new(format!("Invalid operands for instruction {}.", instruction.mnemonic).as_str(), instruction.span));
+    };
+
+    let mut bytes = vec![0; encoded_len(form)];
+
+    bytes[0] = opcode(&instruction.mnemonic, form_index);
+    bytes[1] = instruction.condition.as_deref().map_or(0, condition_code) << 4;
+
+    let mut nibbles = 0;
+    let mut immediates = Vec::new();
+
+    for ((arg, operand), span) in instruction.args.iter().zip(form).zip(&instruction.arg_spans) {
+        match operand {
+            Operand::Immediate(field) => immediates.push((arg, field, *span)),
+            _ => {
+                let value = field_value(arg);
+                match nibbles {
+                    0 => bytes[1] |= value,
+                    1 => bytes[2] |= value << 4,
+                    _ => bytes[2] |= value
+                }
+                nibbles += 1;
+            }
+        }
+    }
+
+    let mut index = if nibbles > 1 { 3 } else { 2 };
+    let mut fixups = Vec::new();
+
+    for (arg, field, span) in immediates {
+        let value = immediate_value(arg, field, span, instruction, resolve)?;
+
+        let encoded = match value.part {
+            AddressPart::Whole => value.constant,
+            _ => value.byte() as i32
+        };
+        let masked = (encoded as u32) & (u32::MAX >> (32 - field.bits));
+
+        if value.base.is_some() {
+            fixups.push(Fixup { offset: index, value });
+        }
+
+        for byte_i in 0..field.bits.div_ceil(8) {
+            bytes[index] = (masked >> (8 * byte_i)) as u8;
+            index += 1;
+        }
+    }
+
+    Ok(EncodedInstruction { bytes, fixups })
+}
+
+
+pub fn instruction_len(instruction: &Instruction) -> Result<usize, CodegenError> {
+    match matching_form(instruction) {
+        Some((_, form)) => Ok(encoded_len(form)),
+        None => Err(CodegenError::new(format!("Invalid operands for instruction {}.", instruction.mnemonic).as_str(), instruction.span))
+    }
+}
+
+
+fn field_value(arg: &InstructionArg) -> u8 {
+    match arg {
+        InstructionArg::Register(name) => register_number(name),
+        InstructionArg::LongRegister(name) => long_register_number(name),
+        InstructionArg::SystemRegister(name) => system_register_number(name),
+        InstructionArg::Port(name) => port_number(name),
+        _ => unreachable!()
+    }
+}
+
+
+fn immediate_value(arg: &InstructionArg, field: &ImmediateField, span: Span, instruction: &Instruction, resolve: &dyn Fn(&LabelAccess) -> Option<Value>) -> Result<Value, CodegenError> {
+    let value = match arg {
+        InstructionArg::Label(label) => match resolve(label) {
+            Some(value) => value,
+            None => return Err(CodegenError::new(format!("Unresolved label {}.", label.str.as_ref().unwrap()).as_str(), label.span))
+        },
+        InstructionArg::Number(expression) if expression.value().is_none() => evaluate(expression, resolve)?,
+        _ => return Ok(Value::constant(arg.immediate_value().unwrap()))
+    };
+
+    match (value.base.is_some(), value.part) {
+        (false, _) => {
+            let (min, max) = field_range(field);
+            let signedness = if field.signed { "signed" } else { "unsigned" };
+
+            if (value.constant as i64) < min || (value.constant as i64) > max {
+                return Err(CodegenError::new(format!("Immediate #{} does not fit the {}-bit {} field of {} (expected #{} to #{}).", value.constant, field.bits, signedness, instruction.mnemonic, min, max).as_str(), span));
+            }
+        },
+        (true, AddressPart::Whole) if field.bits != 16 => {
+            return Err(CodegenError::new(format!("A label address needs a 16-bit field, the field of {} has {} bits.", instruction.mnemonic, field.bits).as_str(), span)
+                .with_help("Use lo() or hi() to take one byte of the address."));
+        },
+        (true, AddressPart::Low | AddressPart::High) if field.bits != 8 => {
+            return Err(CodegenError::new(format!("lo() and hi() need an 8-bit field, the field of {} has {} bits.", instruction.mnemonic, field.bits).as_str(), span));
+        },
+        _ => {}
+    }
+
+    Ok(value)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::opcodes::operand_forms;
+    use crate::lexer::resources::{INSTRUCTION_NAMES, LONG_REGISTER_NAMES, PORT_NAMES, REGISTER_NAMES};
+    use crate::sema::ast::expression::Expression;
+
+    fn span() -> Span {
+        Span::new(0, 0, 0, 1, 1)
+    }
+
+    fn instruction(mnemonic: &str, condition: Option<&str>, args: Vec<InstructionArg>) -> Instruction {
+        let arg_spans = vec![span(); args.len()];
+        Instruction { mnemonic: mnemonic.to_string(), condition: condition.map(str::to_string), args, arg_spans, span: span() }
+    }
+
+    fn register(name: &str) -> InstructionArg {
+        InstructionArg::Register(name.to_string())
+    }
+
+    fn long_register(name: &str) -> InstructionArg {
+        InstructionArg::LongRegister(name.to_string())
+    }
+
+    fn number(value: i32) -> InstructionArg {
+        InstructionArg::Number(Expression::number(value, span()))
+    }
+
+    fn encode(instruction: &Instruction) -> Vec<u8> {
+        encode_instruction(instruction, &|_| None).ok().unwrap().bytes
+    }
+
+    fn args(text: &str) -> Vec<InstructionArg> {
+        text.split_whitespace().map(|arg| {
+            if let Some(value) = arg.strip_prefix("#x") {
+                number(i32::from_str_radix(value, 16).unwrap())
+            } else if let Some(value) = arg.strip_prefix('#') {
+                number(value.parse().unwrap())
+            } else if LONG_REGISTER_NAMES.contains(&arg) {
+                long_register(arg)
+            } else if REGISTER_NAMES.contains(&arg) {
+                register(arg)
+            } else if PORT_NAMES.contains(&arg) {
+                InstructionArg::Port(arg.to_string())
+            } else {
+                InstructionArg::SystemRegister(arg.to_string())
+            }
+        }).collect()
+    }
+
+    #[test]
+    fn every_instruction_has_operand_forms() {
+        for name in INSTRUCTION_NAMES {
+            assert!(!operand_forms(name).is_empty(), "{name} has no operand forms");
+            assert!(operand_forms(name).len() <= 4, "{name} has more forms than fit in the opcode");
+        }
+    }
+
+    #[test]
+    fn encodes_every_form_of_every_instruction() {
+        let cases: [(&str, &str, &[u8]); 100] = [
+            ("mov", "r3 r5", &[0x00, 0x93, 0x50]),
+            ("mov", "r3 #165", &[0x01, 0x93, 0xA5]),
+            ("mov", "lr3 lr5", &[0x02, 0x93, 0x50]),
+            ("mov", "lr3 #xBEEF", &[0x03, 0x93, 0xEF, 0xBE]),
+            ("movs", "r3 r5", &[0x04, 0x93, 0x50]),
+            ("movs", "r3 #165", &[0x05, 0x93, 0xA5]),
+            ("mvn", "r3 r5", &[0x08, 0x93, 0x50]),
+            ("mvn", "r3 #165", &[0x09, 0x93, 0xA5]),
+            ("mvns", "r3 r5", &[0x0C, 0x93, 0x50]),
+            ("mvns", "r3 #165", &[0x0D, 0x93, 0xA5]),
+            ("srw", "psr r5", &[0x10, 0x94, 0x50]),
+            ("srr", "r3 intr", &[0x14, 0x93, 0x50]),
+            ("ldr", "r3 lr5", &[0x18, 0x93, 0x50]),
+            ("ldro", "r3 lr5 #-2", &[0x1C, 0x93, 0x50, 0xFE]),
+            ("ldri", "r3 lr5", &[0x20, 0x93, 0x50]),
+            ("str", "r3 lr5", &[0x24, 0x93, 0x50]),
+            ("stro", "r3 lr5 #-2", &[0x28, 0x93, 0x50, 0xFE]),
+            ("stri", "r3 lr5", &[0x2C, 0x93, 0x50]),
+            ("add", "r3 r5 r1", &[0x30, 0x93, 0x51]),
+            ("add", "r3 r5 #165", &[0x31, 0x93, 0x50, 0xA5]),
+            ("adds", "r3 r5 r1", &[0x34, 0x93, 0x51]),
+            ("adds", "r3 r5 #165", &[0x35, 0x93, 0x50, 0xA5]),
+            ("addc", "r3 r5 r1", &[0x38, 0x93, 0x51]),
+            ("addc", "r3 r5 #165", &[0x39, 0x93, 0x50, 0xA5]),
+            ("addcs", "r3 r5 r1", &[0x3C, 0x93, 0x51]),
+            ("addcs", "r3 r5 #165", &[0x3D, 0x93, 0x50, 0xA5]),
+            ("sub", "r3 r5 r1", &[0x40, 0x93, 0x51]),
+            ("sub", "r3 r5 #165", &[0x41, 0x93, 0x50, 0xA5]),
+            ("subs", "r3 r5 r1", &[0x44, 0x93, 0x51]),
+            ("subs", "r3 r5 #165", &[0x45, 0x93, 0x50, 0xA5]),
+            ("subc", "r3 r5 r1", &[0x48, 0x93, 0x51]),
+            ("subc", "r3 r5 #165", &[0x49, 0x93, 0x50, 0xA5]),
+            ("subcs", "r3 r5 r1", &[0x4C, 0x93, 0x51]),
+            ("subcs", "r3 r5 #165", &[0x4D, 0x93, 0x50, 0xA5]),
+            ("and", "r3 r5 r1", &[0x50, 0x93, 0x51]),
+            ("and", "r3 r5 #165", &[0x51, 0x93, 0x50, 0xA5]),
+            ("ands", "r3 r5 r1", &[0x54, 0x93, 0x51]),
+            ("ands", "r3 r5 #165", &[0x55, 0x93, 0x50, 0xA5]),
+            ("or", "r3 r5 r1", &[0x58, 0x93, 0x51]),
+            ("or", "r3 r5 #165", &[0x59, 0x93, 0x50, 0xA5]),
+            ("ors", "r3 r5 r1", &[0x5C, 0x93, 0x51]),
+            ("ors", "r3 r5 #165", &[0x5D, 0x93, 0x50, 0xA5]),
+            ("eor", "r3 r5 r1", &[0x60, 0x93, 0x51]),
+            ("eor", "r3 r5 #165", &[0x61, 0x93, 0x50, 0xA5]),
+            ("eors", "r3 r5 r1", &[0x64, 0x93, 0x51]),
+            ("eors", "r3 r5 #165", &[0x65, 0x93, 0x50, 0xA5]),
+            ("lsl", "r3 r5 r1", &[0x68, 0x93, 0x51]),
+            ("lsl", "r3 r5 #5", &[0x69, 0x93, 0x50, 0x05]),
+            ("lsls", "r3 r5 r1", &[0x6C, 0x93, 0x51]),
+            ("lsls", "r3 r5 #5", &[0x6D, 0x93, 0x50, 0x05]),
+            ("lsr", "r3 r5 r1", &[0x70, 0x93, 0x51]),
+            ("lsr", "r3 r5 #5", &[0x71, 0x93, 0x50, 0x05]),
+            ("lsrs", "r3 r5 r1", &[0x74, 0x93, 0x51]),
+            ("lsrs", "r3 r5 #5", &[0x75, 0x93, 0x50, 0x05]),
+            ("asr", "r3 r5 r1", &[0x78, 0x93, 0x51]),
+            ("asr", "r3 r5 #5", &[0x79, 0x93, 0x50, 0x05]),
+            ("asrs", "r3 r5 r1", &[0x7C, 0x93, 0x51]),
+            ("asrs", "r3 r5 #5", &[0x7D, 0x93, 0x50, 0x05]),
+            ("cls", "r3 r5 r1", &[0x80, 0x93, 0x51]),
+            ("cls", "r3 r5 #5", &[0x81, 0x93, 0x50, 0x05]),
+            ("csls", "r3 r5 r1", &[0x84, 0x93, 0x51]),
+            ("csls", "r3 r5 #5", &[0x85, 0x93, 0x50, 0x05]),
+            ("csr", "r3 r5 r1", &[0x88, 0x93, 0x51]),
+            ("csr", "r3 r5 #5", &[0x89, 0x93, 0x50, 0x05]),
+            ("csrs", "r3 r5 r1", &[0x8C, 0x93, 0x51]),
+            ("csrs", "r3 r5 #5", &[0x8D, 0x93, 0x50, 0x05]),
+            ("cmn", "r3 r5", &[0x90, 0x93, 0x50]),
+            ("cmn", "r3 #165", &[0x91, 0x93, 0xA5]),
+            ("addcd", "r3 r5", &[0x94, 0x93, 0x50]),
+            ("addcd", "r3 #165", &[0x95, 0x93, 0xA5]),
+            ("cmp", "r3 r5", &[0x98, 0x93, 0x50]),
+            ("cmp", "r3 #165", &[0x99, 0x93, 0xA5]),
+            ("subcd", "r3 r5", &[0x9C, 0x93, 0x50]),
+            ("subcd", "r3 #165", &[0x9D, 0x93, 0xA5]),
+            ("andd", "r3 r5", &[0xA0, 0x93, 0x50]),
+            ("andd", "r3 #165", &[0xA1, 0x93, 0xA5]),
+            ("ord", "r3 r5", &[0xA4, 0x93, 0x50]),
+            ("ord", "r3 #165", &[0xA5, 0x93, 0xA5]),
+            ("eord", "r3 r5", &[0xA8, 0x93, 0x50]),
+            ("eord", "r3 #165", &[0xA9, 0x93, 0xA5]),
+            ("lsld", "r3 r5", &[0xAC, 0x93, 0x50]),
+            ("lsld", "r3 #5", &[0xAD, 0x93, 0x05]),
+            ("lsrd", "r3 r5", &[0xB0, 0x93, 0x50]),
+            ("lsrd", "r3 #5", &[0xB1, 0x93, 0x05]),
+            ("asrd", "r3 r5", &[0xB4, 0x93, 0x50]),
+            ("asrd", "r3 #5", &[0xB5, 0x93, 0x05]),
+            ("csld", "r3 r5", &[0xB8, 0x93, 0x50]),
+            ("csld", "r3 #5", &[0xB9, 0x93, 0x05]),
+            ("csrd", "r3 r5", &[0xBC, 0x93, 0x50]),
+            ("csrd", "r3 #5", &[0xBD, 0x93, 0x05]),
+            ("ba", "lr3", &[0xC0, 0x93, 0x00]),
+            ("bal", "lr3", &[0xC4, 0x93, 0x00]),
+            ("br", "#-2", &[0xC8, 0x90, 0xFE]),
+            ("br", "r3", &[0xC9, 0x93, 0x00]),
+            ("brl", "#-2", &[0xCC, 0x90, 0xFE]),
+            ("brl", "r3", &[0xCD, 0x93, 0x00]),
+            ("ptr", "r3 p5", &[0xD0, 0x93, 0x50]),
+            ("ptw", "p3 r5", &[0xD4, 0x93, 0x50]),
+            ("ptsr", "r3 p5", &[0xD8, 0x93, 0x50]),
+            ("svc", "#165", &[0xDC, 0x90, 0xA5])
+        ];
+
+        for name in INSTRUCTION_NAMES {
+            assert_eq!(cases.iter().filter(|(mnemonic, _, _)| *mnemonic == name).count(), operand_forms(name).len(), "{name}");
+        }
+
+        for (mnemonic, text, expected) in cases {
+            assert_eq!(encode(&instruction(mnemonic, Some("ne"), args(text))), expected, "{mnemonic} {text}");
+        }
+    }
+
+    #[test]
+    fn encodes_documented_examples() {
+        let cases = [
+            (instruction("mov", Some("ne"), vec![register("r3"), register("r12")]), vec![0x00, 0x93, 0xC0]),
+            (instruction("mov", None, vec![register("r1"), number(200)]), vec![0x01, 0x01, 0xC8]),
+            (instruction("mov", None, vec![long_register("lr2"), long_register("lr5")]), vec![0x02, 0x02, 0x50]),
+            (instruction("mov", None, vec![long_register("lr4"), number(0x1234)]), vec![0x03, 0x04, 0x34, 0x12]),
+            (instruction("add", Some("eq"), vec![register("r1"), register("r2"), register("r3")]), vec![0x30, 0x11, 0x23]),
+            (instruction("add", None, vec![register("r1"), register("r2"), number(0xAB)]), vec![0x31, 0x01, 0x20, 0xAB]),
+            (instruction("lsl", None, vec![register("r1"), register("r2"), number(7)]), vec![0x69, 0x01, 0x20, 0x07]),
+            (instruction("ldro", None, vec![register("r1"), long_register("lr2"), number(-2)]), vec![0x1C, 0x01, 0x20, 0xFE]),
+            (instruction("srw", None, vec![InstructionArg::SystemRegister("psr".to_string()), register("r5")]), vec![0x10, 0x04, 0x50]),
+            (instruction("srr", None, vec![register("r5"), InstructionArg::SystemRegister("intr".to_string())]), vec![0x14, 0x05, 0x50]),
+            (instruction("ba", Some("ses"), vec![long_register("lr15")]), vec![0xC0, 0xFF, 0x00]),
+            (instruction("br", None, vec![number(-3)]), vec![0xC8, 0x00, 0xFD]),
+            (instruction("br", Some("geu"), vec![register("r9")]), vec![0xC9, 0xC9, 0x00]),
+            (instruction("ptw", None, vec![InstructionArg::Port("p3".to_string()), register("r1")]), vec![0xD4, 0x03, 0x10]),
+            (instruction("svc", None, vec![number(7)]), vec![0xDC, 0x00, 0x07]),
+            (instruction("mov", None, vec![register("r0"), InstructionArg::String(vec!['A'])]), vec![0x01, 0x00, 0x41])
+        ];
+
+        for (instruction, expected) in cases {
+            assert_eq!(encode(&instruction), expected, "{}", instruction.mnemonic);
+        }
+    }
+
+    #[test]
+    fn encodes_condition_codes_in_the_high_nibble() {
+        for (name, code) in [("al", 0x0), ("zs", 0x1), ("mi", 0x2), ("vs", 0x3), ("cc", 0x4), ("gu", 0x5), ("ss", 0x6), ("gs", 0x7),
+                             ("zc", 0x9), ("pl", 0xA), ("vc", 0xB), ("cs", 0xC), ("seu", 0xD), ("ges", 0xE), ("ses", 0xF)] {
+            let bytes = encode(&instruction("mov", Some(name), vec![register("r2"), register("r7")]));
+            assert_eq!(bytes[1], (code << 4) | 0x2, "condition {name}");
+        }
+    }
+}
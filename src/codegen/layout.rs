@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use crate::codegen::codegen_error::CodegenError;
+use crate::codegen::encoder::instruction_len;
+use crate::sema::ast::file::File;
+use crate::sema::ast::statement::Statement;
+
+
+
+pub const ADDRESS_SPACE_SIZE: u32 = 0x10000;
+
+
+pub struct Layout {
+    pub addresses: Vec<u32>,
+    pub symbols: HashMap<String, u32>,
+    pub size: u32
+}
+
+
+pub fn layout(file: &File) -> Result<Layout, CodegenError> {
+    let mut addresses = Vec::new();
+    let mut symbols = HashMap::new();
+    let mut address: u32 = 0;
+
+    for stmt in &file.statements {
+        addresses.push(address);
+
+        let (size, span) = match stmt {
+            Statement::LabelDirective(node) => {
+                symbols.insert(node.label.str.clone().unwrap(), address);
+                (0, node.span)
+            },
+            Statement::ResDirective(node) => {
+                symbols.insert(node.label.str.clone().unwrap(), address);
+                (node.data_type.size(), node.span)
+            },
+            Statement::Instruction(node) => (instruction_len(node)? as u32, node.span),
+            Statement::Macro(node) => {
+                return Err(CodegenError::new(format!("Macro {} must be expanded before layout.", node.mnemonic).as_str(), node.span));
+            },
+            Statement::ImportDirective(node) => (0, node.span),
+            Statement::ExportDirective(node) => (0, node.span),
+            Statement::StartDirective(node) => (0, node.span),
+            Statement::EquDirective(node) => (0, node.span),
+        };
+
+        address = address.saturating_add(size);
+
+        if address > ADDRESS_SPACE_SIZE {
+            return Err(CodegenError::new("Program does not fit into the 64 KiB address space.", span));
+        }
+    }
+
+    Ok(Layout { addresses, symbols, size: address })
+}
@@ -0,0 +1,173 @@
+use crate::lexer::resources::{INSTRUCTION_NAMES, LONG_REGISTER_NAMES, PORT_NAMES, REGISTER_NAMES, SYSTEM_REGISTER_NAMES};
+use crate::sema::ast::instruction::Instruction;
+use crate::sema::ast::instruction_arg::InstructionArg;
+
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub struct ImmediateField {
+    pub bits: u32,
+    pub signed: bool
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum Operand {
+    Register,
+    LongRegister,
+    SystemRegister,
+    Port,
+    Immediate(ImmediateField)
+}
+
+impl Operand {
+    pub fn desc(&self) -> String {
+        match self {
+            Self::Register => "<register>".to_string(),
+            Self::LongRegister => "<long register>".to_string(),
+            Self::SystemRegister => "<system register>".to_string(),
+            Self::Port => "<port>".to_string(),
+            Self::Immediate(_) => "<immediate>".to_string()
+        }
+    }
+}
+
+
+const R: Operand = Operand::Register;
+const L: Operand = Operand::LongRegister;
+const S: Operand = Operand::SystemRegister;
+const P: Operand = Operand::Port;
+const U3: Operand = Operand::Immediate(ImmediateField { bits: 3, signed: false });
+const U8: Operand = Operand::Immediate(ImmediateField { bits: 8, signed: false });
+const S8: Operand = Operand::Immediate(ImmediateField { bits: 8, signed: true });
+const U16: Operand = Operand::Immediate(ImmediateField { bits: 16, signed: false });
+
+
+static MOV_FORMS: [&[Operand]; 4] = [&[R, R], &[R, U8], &[L, L], &[L, U16]];
+static UNARY_FORMS: [&[Operand]; 2] = [&[R, R], &[R, U8]];
+static ALU_FORMS: [&[Operand]; 2] = [&[R, R, R], &[R, R, U8]];
+static COMPARE_FORMS: [&[Operand]; 2] = [&[R, R], &[R, U8]];
+static SHIFT_FORMS: [&[Operand]; 2] = [&[R, R, R], &[R, R, U3]];
+static SHIFT_COMPARE_FORMS: [&[Operand]; 2] = [&[R, R], &[R, U3]];
+static SYSTEM_WRITE_FORMS: [&[Operand]; 1] = [&[S, R]];
+static SYSTEM_READ_FORMS: [&[Operand]; 1] = [&[R, S]];
+static MEMORY_FORMS: [&[Operand]; 1] = [&[R, L]];
+static MEMORY_OFFSET_FORMS: [&[Operand]; 1] = [&[R, L, S8]];
+static BRANCH_ABSOLUTE_FORMS: [&[Operand]; 1] = [&[L]];
+static BRANCH_RELATIVE_FORMS: [&[Operand]; 2] = [&[S8], &[R]];
+static PORT_READ_FORMS: [&[Operand]; 1] = [&[R, P]];
+static PORT_WRITE_FORMS: [&[Operand]; 1] = [&[P, R]];
+static SVC_FORMS: [&[Operand]; 1] = [&[U8]];
+
+
+pub fn operand_forms(mnemonic: &str) -> &'static [&'static [Operand]] {
+    match mnemonic {
+        "mov" => &MOV_FORMS,
+        "movs" | "mvn" | "mvns" => &UNARY_FORMS,
+        "add" | "adds" | "addc" | "addcs"
+        | "sub" | "subs" | "subc" | "subcs"
+        | "and" | "ands" | "or" | "ors" | "eor" | "eors" => &ALU_FORMS,
+        "cmn" | "addcd" | "cmp" | "subcd" | "andd" | "ord" | "eord" => &COMPARE_FORMS,
+        "lsl" | "lsls" | "lsr" | "lsrs" | "asr" | "asrs"
+        | "cls" | "csls" | "csr" | "csrs" => &SHIFT_FORMS,
+        "lsld" | "lsrd" | "asrd" | "csld" | "csrd" => &SHIFT_COMPARE_FORMS,
+        "srw" => &SYSTEM_WRITE_FORMS,
+        "srr" => &SYSTEM_READ_FORMS,
+        "ldr" | "ldri" | "str" | "stri" => &MEMORY_FORMS,
+        "ldro" | "stro" => &MEMORY_OFFSET_FORMS,
+        "ba" | "bal" => &BRANCH_ABSOLUTE_FORMS,
+        "br" | "brl" => &BRANCH_RELATIVE_FORMS,
+        "ptr" | "ptsr" => &PORT_READ_FORMS,
+        "ptw" => &PORT_WRITE_FORMS,
+        "svc" => &SVC_FORMS,
+        _ => &[]
+    }
+}
+
+
+pub fn matching_form(instruction: &Instruction) -> Option<(usize, &'static [Operand])> {
+    operand_forms(&instruction.mnemonic)
+        .iter()
+        .enumerate()
+        .find(|(_, form)| form.len() == instruction.args.len() && form.iter().zip(&instruction.args).all(|(operand, arg)| arg_matches(arg, operand)))
+        .map(|(i, form)| (i, *form))
+}
+
+
+fn arg_matches(arg: &InstructionArg, operand: &Operand) -> bool {
+    match (arg, operand) {
+        (InstructionArg::Register(_), Operand::Register) => true,
+        (InstructionArg::LongRegister(_), Operand::LongRegister) => true,
+        (InstructionArg::SystemRegister(_), Operand::SystemRegister) => true,
+        (InstructionArg::Port(_), Operand::Port) => true,
+        (InstructionArg::Number(_), Operand::Immediate(_)) => true,
+        (InstructionArg::String(s), Operand::Immediate(_)) => s.len() == 1,
+        (InstructionArg::Label(_), Operand::Immediate(field)) => field.bits == 16,
+        _ => false
+    }
+}
+
+
+pub fn opcode(mnemonic: &str, form_index: usize) -> u8 {
+    let mnemonic_index = INSTRUCTION_NAMES.iter().position(|name| *name == mnemonic).unwrap();
+
+    ((mnemonic_index << 2) | form_index) as u8
+}
+
+
+pub fn encoded_len(form: &[Operand]) -> usize {
+    let mut nibbles = 0;
+    let mut immediate_bytes = 0;
+
+    for operand in form {
+        match operand {
+            Operand::Immediate(field) => immediate_bytes += field.bits.div_ceil(8) as usize,
+            _ => nibbles += 1
+        }
+    }
+
+    let immediate_start = if nibbles > 1 { 3 } else { 2 };
+
+    usize::max(3, immediate_start + immediate_bytes)
+}
+
+
+pub fn condition_code(name: &str) -> u8 {
+    match name {
+        "al" => 0x0,
+        "eq" | "zs" => 0x1,
+        "mi" => 0x2,
+        "vs" => 0x3,
+        "su" | "cc" => 0x4,
+        "gu" => 0x5,
+        "ss" => 0x6,
+        "gs" => 0x7,
+        "ne" | "zc" => 0x9,
+        "pl" => 0xa,
+        "vc" => 0xb,
+        "geu" | "cs" => 0xc,
+        "seu" => 0xd,
+        "ges" => 0xe,
+        "ses" => 0xf,
+        _ => unreachable!()
+    }
+}
+
+
+pub fn register_number(name: &str) -> u8 {
+    REGISTER_NAMES.iter().position(|r| *r == name).unwrap() as u8
+}
+
+pub fn long_register_number(name: &str) -> u8 {
+    LONG_REGISTER_NAMES.iter().position(|r| *r == name).unwrap() as u8
+}
+
+pub fn system_register_number(name: &str) -> u8 {
+    SYSTEM_REGISTER_NAMES.iter().position(|r| *r == name).unwrap() as u8
+}
+
+pub fn port_number(name: &str) -> u8 {
+    PORT_NAMES.iter().position(|p| *p == name).unwrap() as u8
+}
@@ -0,0 +1,4 @@
+pub mod diagnostic;
+pub mod renderer;
+pub mod json;
+pub mod pragma;
@@ -0,0 +1,57 @@
+use crate::span::Span;
+
+
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning
+}
+
+impl Severity {
+    pub fn desc(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning"
+        }
+    }
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub span: Option<Span>,
+    pub file: Option<String>,
+    pub notes: Vec<String>,
+    pub help: Option<String>
+}
+
+impl Diagnostic {
+    pub fn error(code: &'static str, message: &str, span: Option<Span>) -> Self {
+        Diagnostic { severity: Severity::Error, code, message: message.to_string(), span, file: None, notes: Vec::new(), help: None }
+    }
+
+    pub fn warning(code: &'static str, message: &str, span: Option<Span>) -> Self {
+        Diagnostic { severity: Severity::Warning, code, message: message.to_string(), span, file: None, notes: Vec::new(), help: None }
+    }
+
+    pub fn with_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+
+    pub fn with_note(mut self, note: &str) -> Self {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    pub fn with_help(mut self, help: &str) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+}
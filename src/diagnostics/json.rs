@@ -0,0 +1,72 @@
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::source_map::SourceMap;
+use crate::span::Span;
+
+
+
+pub fn to_json(diagnostic: &Diagnostic, sources: &SourceMap) -> String {
+    let name = match (&diagnostic.file, diagnostic.span) {
+        (Some(file), _) => string(file),
+        (None, Some(span)) => string(&sources.file(span.file).name),
+        (None, None) => sources.files.first().map_or("null".to_string(), |file| string(&file.name))
+    };
+
+    let (line, column, end_line, end_column) = match diagnostic.span {
+        Some(span) => {
+            let (end_line, end_column) = end_position(&sources.file(span.file).source, span);
+            (span.line.to_string(), span.column.to_string(), end_line.to_string(), end_column.to_string())
+        },
+        None => ("null".to_string(), "null".to_string(), "null".to_string(), "null".to_string())
+    };
+
+    let notes: Vec<String> = diagnostic.notes.iter().map(|note| string(note)).collect();
+    let help = diagnostic.help.as_deref().map_or("null".to_string(), string);
+    let included_from: Vec<String> = diagnostic.span.map(|span| sources.include_chain(span.file)).unwrap_or_default().iter()
+        .map(|(name, line)| format!("{{\"file\":{},\"line\":{line}}}", string(name)))
+        .collect();
+
+    format!(
+        "{{\"file\":{name},\"severity\":{},\"code\":{},\"message\":{},\"line\":{line},\"column\":{column},\"end_line\":{end_line},\"end_column\":{end_column},\"notes\":[{}],\"help\":{help},\"included_from\":[{}]}}",
+        string(diagnostic.severity.desc()),
+        string(diagnostic.code),
+        string(&diagnostic.message),
+        notes.join(","),
+        included_from.join(",")
+    )
+}
+
+
+fn end_position(source: &str, span: Span) -> (u32, u32) {
+    let start = span.start.min(source.len());
+    let end = span.end.clamp(start, source.len());
+    let covered = &source[start..end];
+
+    if covered == "\n" {
+        return (span.line, span.column + 1);
+    }
+
+    match covered.rfind('\n') {
+        Some(i) => (span.line + covered.matches('\n').count() as u32, covered[i + 1..].chars().count() as u32 + 1),
+        None => (span.line, span.column + covered.chars().count() as u32)
+    }
+}
+
+
+fn string(text: &str) -> String {
+    let mut out = String::from("\"");
+
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c)
+        }
+    }
+
+    out.push('"');
+    out
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::sema::sema_warning::WarningKind;
+use crate::span::Span;
+
+
+
+#[derive(Debug)]
+#[derive(Default)]
+pub struct Suppressions {
+    lines: HashSet<(usize, u32, WarningKind)>,
+    files: HashSet<(usize, WarningKind)>
+}
+
+impl Suppressions {
+    pub fn allows(&self, kind: WarningKind, span: Span) -> bool {
+        self.files.contains(&(span.file, kind)) || self.lines.contains(&(span.file, span.line, kind))
+    }
+}
+
+
+pub fn parse_pragmas(source: &str, file: usize, suppressions: &mut Suppressions) -> Vec<Diagnostic> {
+    let mut errors = Vec::new();
+    let mut line_offset = 0;
+
+    for (line_i, text) in source.split('\n').enumerate() {
+        let line = line_i as u32 + 1;
+
+        if let Some((comment, file_wide, names_offset)) = pragma(text) {
+            let target_line = if text[..comment].trim().is_empty() { line + 1 } else { line };
+
+            for (name_offset, name) in words(&text[names_offset..]) {
+                let start = names_offset + name_offset;
+
+                match WarningKind::from_name(name) {
+                    Some(kind) if file_wide => { suppressions.files.insert((file, kind)); },
+                    Some(kind) => { suppressions.lines.insert((file, target_line, kind)); },
+                    None => {
+                        let span = Span::new(file, line_offset + start, line_offset + start + name.len(), line, text[..start].chars().count() as u32 + 1);
+                        let known: Vec<&str> = WarningKind::ALL.iter().map(|kind| kind.name()).collect();
+                        errors.push(Diagnostic::error("E0003", format!("Unknown warning {} in @allow pragma.", name).as_str(), Some(span))
+                            .with_note(format!("Known warnings are {}.", known.join(", ")).as_str()));
+                    }
+                }
+            }
+        }
+
+        line_offset += text.len() + 1;
+    }
+
+    errors
+}
+
+
+fn pragma(text: &str) -> Option<(usize, bool, usize)> {
+    let comment = comment_start(text)?;
+    let body = &text[comment + 1..];
+    let trimmed = body.trim_start();
+    let keyword_offset = comment + 1 + body.len() - trimmed.len();
+
+    for (keyword, file_wide) in [("@allow-file", true), ("@allow", false)] {
+        if let Some(names) = trimmed.strip_prefix(keyword) && (names.is_empty() || names.starts_with(char::is_whitespace)) {
+            return Some((comment, file_wide, keyword_offset + keyword.len()));
+        }
+    }
+
+    None
+}
+
+
+fn comment_start(text: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        match (c, quote) {
+            ('\\', Some(_)) => escaped = true,
+            ('"' | '\'', None) => quote = Some(c),
+            (_, Some(q)) if c == q => quote = None,
+            (';', None) => return Some(i),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+
+fn words(text: &str) -> Vec<(usize, &str)> {
+    let mut result = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        let separator = c.is_whitespace() || c == ',';
+
+        match start {
+            Some(s) if separator => {
+                result.push((s, &text[s..i]));
+                start = None;
+            },
+            None if !separator => start = Some(i),
+            _ => {}
+        }
+    }
+
+    result
+}
@@ -0,0 +1,67 @@
+use colorize::AnsiColor;
+
+use crate::diagnostics::diagnostic::{Diagnostic, Severity};
+use crate::source_map::SourceMap;
+use crate::span::Span;
+
+
+
+pub fn render(diagnostic: &Diagnostic, sources: &SourceMap, colour: bool) -> String {
+    let paint = |text: String, style: fn(String) -> String| if colour { style(text) } else { text };
+    let accent: fn(String) -> String = match diagnostic.severity {
+        Severity::Error => |text| text.red().bold(),
+        Severity::Warning => |text| text.yellow().bold()
+    };
+    let gutter_style: fn(String) -> String = |text| text.blue().bold();
+
+    let mut out = format!(
+        "{}{}\n",
+        paint(format!("{}[{}]", diagnostic.severity.desc(), diagnostic.code), accent),
+        paint(format!(": {}", diagnostic.message), |text| text.bold())
+    );
+
+    let line_number = diagnostic.span.map(|span| span.line.to_string()).unwrap_or_default();
+    let gutter = " ".repeat(line_number.len());
+
+    if let Some(span) = diagnostic.span {
+        let file = sources.file(span.file);
+        let (text, padding, width) = snippet(&file.source, span);
+
+        out.push_str(&format!("{}{}:{}:{}\n", paint(format!("{gutter}--> "), gutter_style), file.name, span.line, span.column));
+        out.push_str(&format!("{}\n", paint(format!("{gutter} |"), gutter_style)));
+        out.push_str(&format!("{} {text}\n", paint(format!("{line_number} |"), gutter_style)));
+        out.push_str(&format!("{} {padding}{}\n", paint(format!("{gutter} |"), gutter_style), paint("^".repeat(width), accent)));
+    }
+
+    if let (None, Some(file)) = (diagnostic.span, &diagnostic.file) {
+        out.push_str(&format!("{}{file}\n", paint(format!("{gutter}--> "), gutter_style)));
+    }
+
+    for (name, line) in diagnostic.span.map(|span| sources.include_chain(span.file)).unwrap_or_default() {
+        out.push_str(&format!("{} included from {name}:{line}\n", paint(format!("{gutter} = note:"), gutter_style)));
+    }
+
+    for note in &diagnostic.notes {
+        out.push_str(&format!("{} {note}\n", paint(format!("{gutter} = note:"), gutter_style)));
+    }
+
+    if let Some(help) = &diagnostic.help {
+        out.push_str(&format!("{} {help}\n", paint(format!("{gutter} = help:"), gutter_style)));
+    }
+
+    out
+}
+
+
+fn snippet(source: &str, span: Span) -> (&str, String, usize) {
+    let start = span.start.min(source.len());
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[start..].find('\n').map_or(source.len(), |i| start + i);
+    let end = span.end.clamp(start, line_end);
+
+    let text = source[line_start..line_end].trim_end_matches('\r');
+    let padding = source[line_start..start].chars().map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+    let width = source[start..end].chars().count().max(1);
+
+    (text, padding, width)
+}
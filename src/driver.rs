@@ -0,0 +1,195 @@
+use std::fs;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+
+use asmc_rust::diagnostics::diagnostic::{Diagnostic, Severity};
+use asmc_rust::diagnostics::json::to_json;
+use asmc_rust::diagnostics::renderer::render;
+use asmc_rust::linker::intel_hex::write_intel_hex;
+use asmc_rust::linker::{link, LinkObject};
+use asmc_rust::object::object_file::OBJECT_MAGIC;
+use asmc_rust::object::reader::read_object;
+use asmc_rust::object::writer::write_object;
+use asmc_rust::sema::sema_warning::WarningKind;
+use asmc_rust::source_map::SourceMap;
+use asmc_rust::{assemble, Artifact, Stage};
+
+use crate::cli::{Color, DiagnosticFormat, Emit, Format, Options};
+
+
+
+pub fn run(options: &Options) -> bool {
+    let mut success = true;
+    let mut objects = Vec::new();
+
+    let enabled_warnings = if options.no_warnings { Vec::new() } else { options.enabled_warnings.clone() };
+    let warnings_as_errors = if options.warnings_as_errors { enabled_warnings.clone() } else { options.error_warnings.clone() };
+
+    for input in &options.inputs {
+        let bytes = match fs::read(input) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                report_error(Diagnostic::error("E0001", &format!("Could not read the file: {e}."), None).with_file(input), options);
+                success = false;
+                continue;
+            }
+        };
+
+        if bytes.starts_with(&OBJECT_MAGIC) {
+            if options.emit != Emit::Bin {
+                report_error(Diagnostic::error("E0001", "Object files are only accepted with --emit bin.", None).with_file(input), options);
+                success = false;
+                continue;
+            }
+
+            match read_object(&bytes) {
+                Ok(object) => objects.push(LinkObject { name: input.clone(), object }),
+                Err(e) => {
+                    report_error(e.diagnostic().with_file(input), options);
+                    success = false;
+                }
+            }
+            continue;
+        }
+
+        let Ok(src) = String::from_utf8(bytes) else {
+            report_error(Diagnostic::error("E0001", "The file is not valid UTF-8.", None).with_file(input), options);
+            success = false;
+            continue;
+        };
+
+        let assemble_options = asmc_rust::Options {
+            stop_after: match options.emit {
+                Emit::Tokens => Stage::Tokens,
+                Emit::Cst => Stage::Cst,
+                Emit::Ast => Stage::Ast,
+                Emit::Obj | Emit::Bin => Stage::Object
+            },
+            enabled_warnings: enabled_warnings.clone(),
+            warnings_as_errors: warnings_as_errors.clone(),
+            include_paths: options.include_paths.clone(),
+            max_errors: options.max_errors
+        };
+
+        let output = match assemble(input, &src, &assemble_options) {
+            Ok(output) => {
+                report_warnings(&output.warnings, &output.sources, options);
+                output
+            },
+            Err(diagnostics) => {
+                report_warnings(&diagnostics.warnings, &diagnostics.sources, options);
+                for e in &diagnostics.errors {
+                    report_diagnostic(e, &diagnostics.sources, options);
+                }
+                success = false;
+                continue;
+            }
+        };
+
+        match output.artifact {
+            Artifact::Tokens(tokens) => success &= write_text(options.output.as_deref(), &tokens.iter().map(|token| format!("{token}\n")).collect::<String>(), options),
+            Artifact::Cst(cst) => success &= write_text(options.output.as_deref(), &format!("{:#?}\n", cst), options),
+            Artifact::Ast(file) => success &= write_text(options.output.as_deref(), &format!("{:#?}\n", file), options),
+            Artifact::Object(object) if options.emit == Emit::Obj => {
+                let path = match &options.output {
+                    Some(path) => path.clone(),
+                    None => Path::new(input).with_extension("o").to_string_lossy().into_owned()
+                };
+                success &= write_file(&path, &write_object(&object), options);
+            },
+            Artifact::Object(object) => objects.push(LinkObject { name: input.clone(), object })
+        }
+    }
+
+    if options.emit != Emit::Bin || !success {
+        return success;
+    }
+
+    match link(&objects) {
+        Ok(image) => {
+            let kind = WarningKind::RawEntryPoint;
+
+            if let Some(entry) = image.entry.filter(|entry| *entry != 0 && options.format == Format::Raw && enabled_warnings.contains(&kind)) {
+                let mut diagnostic = Diagnostic::warning(kind.code(), &format!("A raw image does not record the entry point 0x{entry:04X}. [{}]", kind.name()), None).with_help("Use --format ihex to keep it.");
+
+                if warnings_as_errors.contains(&kind) {
+                    diagnostic.severity = Severity::Error;
+                    diagnostic.notes.push(format!("-Werror turned the {} warning into an error.", kind.name()));
+                    report_error(diagnostic, options);
+                    return false;
+                }
+
+                report_diagnostic(&diagnostic, &SourceMap::default(), options);
+            }
+
+            let (bytes, default_path) = match options.format {
+                Format::Raw => (image.data, "a.bin"),
+                Format::IntelHex => (write_intel_hex(&image).into_bytes(), "a.hex")
+            };
+            write_file(options.output.as_deref().unwrap_or(default_path), &bytes, options)
+        },
+        Err(errors) => {
+            for e in errors {
+                report_error(e.diagnostic(), options);
+            }
+            false
+        }
+    }
+}
+
+
+fn write_text(path: Option<&str>, text: &str, options: &Options) -> bool {
+    match path {
+        Some(path) => write_file(path, text.as_bytes(), options),
+        None => {
+            print!("{text}");
+            true
+        }
+    }
+}
+
+
+fn write_file(path: &str, bytes: &[u8], options: &Options) -> bool {
+    let result = if path == "-" { std::io::stdout().write_all(bytes) } else { fs::write(path, bytes) };
+
+    match result {
+        Ok(_) => true,
+        Err(e) => {
+            report_error(Diagnostic::error("E0001", &format!("Could not write the file: {e}."), None).with_file(path), options);
+            false
+        }
+    }
+}
+
+
+fn report_error(diagnostic: Diagnostic, options: &Options) {
+    report_diagnostic(&diagnostic, &SourceMap::default(), options);
+}
+
+
+fn report_warnings(warnings: &[Diagnostic], sources: &SourceMap, options: &Options) {
+    if options.no_warnings {
+        return;
+    }
+
+    for w in warnings {
+        report_diagnostic(w, sources, options);
+    }
+}
+
+
+fn report_diagnostic(diagnostic: &Diagnostic, sources: &SourceMap, options: &Options) {
+    match options.diagnostic_format {
+        DiagnosticFormat::Text => eprintln!("{}", render(diagnostic, sources, use_colour(options))),
+        DiagnosticFormat::Json => eprintln!("{}", to_json(diagnostic, sources))
+    }
+}
+
+
+fn use_colour(options: &Options) -> bool {
+    match options.color {
+        Color::Auto => std::io::stderr().is_terminal(),
+        Color::Always => true,
+        Color::Never => false
+    }
+}
@@ -1,23 +1,43 @@
 use token::{Token, TokenKind};
 use lexer_error::LexerError;
+use trivia::{Trivia, TriviaKind};
+
+use crate::span::Span;
 
 
 
 pub mod token;
 pub mod lexer_error;
+pub mod trivia;
 pub mod resources;
+pub mod includes;
 
 
 pub struct Lexer<'a> {
+    file: usize,
     chars: &'a [char],
+    offsets: &'a [usize],
     index: usize,
-    line: u32
+    line: u32,
+    line_start: usize,
+    max_errors: usize,
+    keep_trivia: bool,
+    pending_trivia: Vec<Trivia>
 }
 
 impl Lexer<'_> {
-    pub fn tokenise(src: &str) -> Result<Vec<Token>, LexerError> {
+    pub fn tokenise(src: &str, file: usize, max_errors: usize) -> (Vec<Token>, Vec<LexerError>) {
+        Self::tokenise_impl(src, file, max_errors, false)
+    }
+
+    pub fn tokenise_with_trivia(src: &str, file: usize, max_errors: usize) -> (Vec<Token>, Vec<LexerError>) {
+        Self::tokenise_impl(src, file, max_errors, true)
+    }
+
+    fn tokenise_impl(src: &str, file: usize, max_errors: usize, keep_trivia: bool) -> (Vec<Token>, Vec<LexerError>) {
         let chars: Vec<char> = src.chars().collect();
-        let mut lexer = Lexer { chars: &chars, index: 0, line: 1 };
+        let offsets: Vec<usize> = src.char_indices().map(|(i, _)| i).chain(std::iter::once(src.len())).collect();
+        let mut lexer = Lexer { file, chars: &chars, offsets: &offsets, index: 0, line: 1, line_start: 0, max_errors, keep_trivia, pending_trivia: Vec::new() };
 
         lexer._tokenise()
     }
@@ -25,12 +45,35 @@ impl Lexer<'_> {
 
 
 impl<'a> Lexer<'a> {
+    fn span_from(&self, start: usize) -> Span {
+        Span::new(self.file, self.offsets[start], self.offsets[self.index], self.line, (start - self.line_start + 1) as u32)
+    }
+
+    fn push_token(&mut self, tokens: &mut Vec<Token>, mut token: Token) {
+        token.leading_trivia = std::mem::take(&mut self.pending_trivia);
+        tokens.push(token);
+    }
+
+    fn push_trivia(&mut self, tokens: &mut [Token], kind: TriviaKind, start: usize) {
+        if !self.keep_trivia {
+            return;
+        }
+
+        let trivia = Trivia { kind, text: self.chars[start..self.index].iter().collect(), span: self.span_from(start) };
+
+        match tokens.last_mut() {
+            Some(token) if self.pending_trivia.is_empty() && token.lexeme != "\n" => token.trailing_trivia.push(trivia),
+            _ => self.pending_trivia.push(trivia)
+        }
+    }
+
     fn is_word_char(c: char) -> bool {
         c.is_ascii_alphanumeric() || (c == '_')
     }
 
     fn is_punctation_character(c: char) -> bool {
         (c == '{') || (c == '}') || (c == ':') || (c == '*') || (c == '>') || (c == '(') || (c == ')') || (c == '$')
+            || (c == '+') || (c == '-') || (c == '/') || (c == '%') || (c == '<') || (c == '&') || (c == '|') || (c == '^') || (c == '~')
     }
 
 
@@ -43,6 +86,18 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    fn is_word_end(c: char) -> bool {
+        c.is_ascii_whitespace() || c == ';' || Self::is_punctation_character(c)
+    }
+
+    fn skip_word(&mut self) {
+        let chars_c = self.chars.len();
+
+        while self.index < chars_c && !Self::is_word_end(self.chars[self.index]) {
+            self.index += 1;
+        }
+    }
+
     fn get_word_token_kind(lexeme: &str) -> TokenKind {
         if resources::INSTRUCTION_NAMES.contains(&lexeme) {
             TokenKind::Instruction
@@ -72,7 +127,8 @@ impl<'a> Lexer<'a> {
     }
 
 
-    fn make_word_token(&mut self) -> Token {
+    fn make_word_token(&mut self) -> Result<Token, LexerError> {
+        let start = self.index;
         let mut lexeme = String::new();
         
 
@@ -85,12 +141,30 @@ impl<'a> Lexer<'a> {
         }
 
 
+        if lexeme.starts_with(|c: char| c.is_ascii_digit()) && lexeme.chars().all(|c| c.is_ascii_digit() || c == '_') {
+            return self.make_bare_number_lit_token(lexeme, start);
+        }
+
         let kind = Self::get_word_token_kind(&lexeme);
-        Token::new(kind, lexeme, self.line)
+        Ok(Token::new(kind, lexeme, self.span_from(start)))
+    }
+
+
+    fn make_bare_number_lit_token(&mut self, lexeme: String, start: usize) -> Result<Token, LexerError> {
+        if lexeme.ends_with('_') {
+            return Err(LexerError::new(lexer_error::LexerErrorKind::InvalidNumberLit(lexeme), self.span_from(start)));
+        }
+
+        if Self::number_lit_value(&lexeme).is_none() {
+            return Err(LexerError::new(lexer_error::LexerErrorKind::NumberLitOverflow(lexeme), self.span_from(start)));
+        }
+
+        Ok(Token::new(TokenKind::Number, lexeme, self.span_from(start)))
     }
 
 
     fn make_macro_token(&mut self) -> Result<Token, LexerError> {
+        let start = self.index;
         let mut lexeme = String::new();
         
 
@@ -106,14 +180,15 @@ impl<'a> Lexer<'a> {
         }
 
         if Self::is_macro_name(&lexeme) {
-            Ok(Token::new(TokenKind::Macro, lexeme, self.line))
+            Ok(Token::new(TokenKind::Macro, lexeme, self.span_from(start)))
         } else {
-            Err(LexerError::new(lexer_error::LexerErrorKind::InvalidMacro(lexeme), self.line))
+            Err(LexerError::new(lexer_error::LexerErrorKind::InvalidMacro(lexeme), self.span_from(start)))
         }
     }
 
 
     fn make_directive_token(&mut self) -> Result<Token, LexerError> {
+        let start = self.index;
         let mut lexeme = String::new();
         
 
@@ -129,9 +204,9 @@ impl<'a> Lexer<'a> {
         }
 
         if Self::is_directive_name(&lexeme) {
-            Ok(Token::new(TokenKind::Directive, lexeme, self.line))
+            Ok(Token::new(TokenKind::Directive, lexeme, self.span_from(start)))
         } else {
-            Err(LexerError::new(lexer_error::LexerErrorKind::InvalidDirective(lexeme), self.line))
+            Err(LexerError::new(lexer_error::LexerErrorKind::InvalidDirective(lexeme), self.span_from(start)))
         }
     }
 
@@ -151,6 +226,7 @@ impl<'a> Lexer<'a> {
     }
 
     fn make_number_lit_token(&mut self) -> Result<Token, LexerError> {
+        let start = self.index;
         let mut lexeme = String::new();
         
 
@@ -162,40 +238,115 @@ impl<'a> Lexer<'a> {
 
         
 
-        let radix_prefix: char;
+        let radix_prefix = match self.chars.get(self.index) {
+            Some(&c) if Self::is_radix_prefix(c) => {
+                lexeme.push(c);
+                self.index += 1;
+                c
+            },
+            _ => 'd'
+        };
 
-        if Self::is_radix_prefix(self.chars[self.index]) {
-            lexeme.push(self.chars[self.index]);
-            radix_prefix = self.chars[self.index];
+        if self.chars.get(self.index) == Some(&'-') {
+            lexeme.push('-');
             self.index += 1;
-        } else {
-            lexeme.push('d');
-            radix_prefix = 'd';
         }
 
-        if self.chars[self.index] == '-' {
-            lexeme.push(self.chars[self.index]);
-            self.index += 1;
-        }
+        let digits_start = lexeme.len();
         
-        while self.index < chars_c && Self::is_valid_digit(radix_prefix, self.chars[self.index]) {
+        while self.index < chars_c && (Self::is_valid_digit(radix_prefix, self.chars[self.index]) || (self.chars[self.index] == '_' && lexeme.len() > digits_start)) {
             lexeme.push(self.chars[self.index]);
             self.index += 1;
         }
 
-        if lexeme.len() == 2 {
-            return Err(LexerError::new(lexer_error::LexerErrorKind::InvalidNumberLit(lexeme), self.line));
+        if lexeme.len() == digits_start || lexeme.ends_with('_') || (self.index < chars_c && !Self::is_word_end(self.chars[self.index])) {
+            self.skip_word();
+            let lexeme = self.chars[start..self.index].iter().collect();
+            return Err(LexerError::new(lexer_error::LexerErrorKind::InvalidNumberLit(lexeme), self.span_from(start)));
         }
 
-        Ok(Token::new(TokenKind::Number, lexeme, self.line))
+        if Self::number_lit_value(&lexeme).is_none() {
+            let lexeme = self.chars[start..self.index].iter().collect();
+            return Err(LexerError::new(lexer_error::LexerErrorKind::NumberLitOverflow(lexeme), self.span_from(start)));
+        }
+
+        Ok(Token::new(TokenKind::Number, lexeme, self.span_from(start)))
+    }
+
+
+    pub fn number_lit_value(lexeme: &str) -> Option<i32> {
+        let Some(literal) = lexeme.strip_prefix('#') else {
+            return lexeme.replace('_', "").parse().ok();
+        };
+
+        let (radix, digits) = match literal.chars().next()? {
+            'b' => (2, &literal[1..]),
+            'o' => (8, &literal[1..]),
+            'd' => (10, &literal[1..]),
+            'x' => (16, &literal[1..]),
+            _ => (10, literal)
+        };
+        let digits: String = digits.chars().filter(|c| *c != '_').collect();
+
+        i32::from_str_radix(&digits, radix).ok()
     }
 
 
     fn is_valid_string_char(c: char) -> bool {
-        !c.is_ascii_control()
+        !c.is_ascii_control() && (c as u32) < 256
+    }
+
+    fn escape_len(&self) -> usize {
+        match self.chars.get(self.index + 1) {
+            Some('n' | 't' | '0' | '\\' | '"') => 2,
+            Some('x') if self.chars.get(self.index + 2..self.index + 4).is_some_and(|digits| digits.iter().all(|c| c.is_ascii_hexdigit())) => 4,
+            _ => 0
+        }
+    }
+
+    fn skip_string(&mut self) {
+        let chars_c = self.chars.len();
+
+        while self.index < chars_c && self.chars[self.index] != '"' && self.chars[self.index] != '\n' {
+            if self.chars[self.index] == '\\' && self.index + 1 < chars_c && self.chars[self.index + 1] != '\n' {
+                self.index += 1;
+            }
+            self.index += 1;
+        }
+        if self.index < chars_c && self.chars[self.index] == '"' {
+            self.index += 1;
+        }
+    }
+
+    fn make_char_lit_token(&mut self) -> Result<Token, LexerError> {
+        let start = self.index;
+        let chars_c = self.chars.len();
+
+        self.index += 1;
+
+        let len = match self.chars.get(self.index) {
+            Some('\\') => self.escape_len(),
+            Some(&c) if c != '\'' && Self::is_valid_string_char(c) => 1,
+            _ => 0
+        };
+
+        if len == 0 || self.chars.get(self.index + len) != Some(&'\'') {
+            match self.chars[self.index.min(chars_c)..].iter().take_while(|c| **c != '\n').position(|c| *c == '\'') {
+                Some(quote) => self.index += quote + 1,
+                None => self.skip_word()
+            }
+            let lexeme = self.chars[start..self.index].iter().collect();
+            return Err(LexerError::new(lexer_error::LexerErrorKind::InvalidCharLit(lexeme), self.span_from(start)));
+        }
+
+        self.index += len + 1;
+        let lexeme = self.chars[start..self.index].iter().collect();
+
+        Ok(Token::new(TokenKind::Char, lexeme, self.span_from(start)))
     }
 
     fn make_string_lit_token(&mut self) -> Result<Token, LexerError> {
+        let start = self.index;
         let mut lexeme = String::new();
         
 
@@ -205,22 +356,45 @@ impl<'a> Lexer<'a> {
         lexeme.push(self.chars[self.index]);
         self.index += 1;
 
-        while self.index < chars_c && self.chars[self.index] != '"' {
+        while self.index < chars_c && self.chars[self.index] != '"' && self.chars[self.index] != '\n' {
+            if self.chars[self.index] == '\\' {
+                let escape_start = self.index;
+                let escape_len = self.escape_len();
+
+                if escape_len == 0 {
+                    self.index = (escape_start + 2).min(chars_c);
+                    if self.index > escape_start + 1 && self.chars[self.index - 1] == '\n' {
+                        self.index -= 1;
+                    }
+                    let escape = self.chars[escape_start..self.index].iter().collect();
+                    let error = LexerError::new(lexer_error::LexerErrorKind::InvalidEscape(escape), self.span_from(escape_start));
+                    self.skip_string();
+                    return Err(error);
+                }
+
+                lexeme.extend(&self.chars[escape_start..escape_start + escape_len]);
+                self.index += escape_len;
+                continue;
+            }
             if !Self::is_valid_string_char(self.chars[self.index]) {
-                return Err(LexerError::new(lexer_error::LexerErrorKind::InvalidCharacterInString(self.chars[self.index]), self.line));
+                let char_start = self.index;
+                self.index += 1;
+                let error = LexerError::new(lexer_error::LexerErrorKind::InvalidCharacterInString(self.chars[char_start]), self.span_from(char_start));
+                self.skip_string();
+                return Err(error);
             }
             lexeme.push(self.chars[self.index]);
             self.index += 1;
         }
-        if self.index >= chars_c {
-            return Err(LexerError::new(lexer_error::LexerErrorKind::UnterminatedString, self.line));
+        if self.index >= chars_c || self.chars[self.index] == '\n' {
+            return Err(LexerError::new(lexer_error::LexerErrorKind::UnterminatedString, self.span_from(start)));
         }
 
         lexeme.push(self.chars[self.index]);
         self.index += 1;
 
 
-        Ok(Token::new(TokenKind::String, lexeme, self.line))
+        Ok(Token::new(TokenKind::String, lexeme, self.span_from(start)))
     }
 
 
@@ -230,60 +404,95 @@ impl<'a> Lexer<'a> {
 
 
 
-    pub fn _tokenise(&mut self) -> Result<Vec<Token>, LexerError> {
+    pub fn _tokenise(&mut self) -> (Vec<Token>, Vec<LexerError>) {
 
 
         let mut tokens: Vec<Token> = Vec::new();
+        let mut errors: Vec<LexerError> = Vec::new();
         
 
         let chars_c = self.chars.len();
-        while self.index < chars_c {
+        while self.index < chars_c && (self.max_errors == 0 || errors.len() < self.max_errors) {
 
             let char = self.chars[self.index];
 
             if char == ';' {
+                let start = self.index;
                 self.drop_comment();
+                self.push_trivia(&mut tokens, TriviaKind::Comment, start);
 
             } else if Self::is_word_char(char) {
-                let new_word_token = self.make_word_token();
-                tokens.push(new_word_token);
+                match self.make_word_token() {
+                    Ok(new_word_token) => self.push_token(&mut tokens, new_word_token),
+                    Err(e) => errors.push(e)
+                }
 
             } else if char == '!' {
-                let new_macro_token = self.make_macro_token()?;
-                tokens.push(new_macro_token);
+                match self.make_macro_token() {
+                    Ok(new_macro_token) => self.push_token(&mut tokens, new_macro_token),
+                    Err(e) => errors.push(e)
+                }
 
             } else if char == '.' {
-                let new_directive_token = self.make_directive_token()?;
-                tokens.push(new_directive_token);
+                match self.make_directive_token() {
+                    Ok(new_directive_token) => self.push_token(&mut tokens, new_directive_token),
+                    Err(e) => errors.push(e)
+                }
+
+            } else if char == '#' && self.chars.get(self.index + 1) == Some(&'(') {
+                let start = self.index;
+                self.index += 1;
+                self.push_token(&mut tokens, Token::new(TokenKind::Punctuation, String::from(char), self.span_from(start)));
 
             } else if char == '#' {
-                let new_number_token = self.make_number_lit_token()?;
-                tokens.push(new_number_token);
+                match self.make_number_lit_token() {
+                    Ok(new_number_token) => self.push_token(&mut tokens, new_number_token),
+                    Err(e) => errors.push(e)
+                }
+
+            } else if char == '\'' {
+                match self.make_char_lit_token() {
+                    Ok(new_char_token) => self.push_token(&mut tokens, new_char_token),
+                    Err(e) => errors.push(e)
+                }
 
             } else if char == '"' {
-                let new_string_token = self.make_string_lit_token()?;
-                tokens.push(new_string_token);
+                match self.make_string_lit_token() {
+                    Ok(new_string_token) => self.push_token(&mut tokens, new_string_token),
+                    Err(e) => errors.push(e)
+                }
 
             } else if Self::is_punctation_character(char) {
-                tokens.push(Token::new(TokenKind::Punctuation, String::from(self.chars[self.index]), self.line));
+                let start = self.index;
                 self.index += 1;
+                self.push_token(&mut tokens, Token::new(TokenKind::Punctuation, String::from(char), self.span_from(start)));
 
             } else if char == '\n' { 
-                tokens.push(Token::new(TokenKind::Punctuation, String::from("\n"), self.line));
+                let start = self.index;
                 self.index += 1;
+                self.push_token(&mut tokens, Token::new(TokenKind::Punctuation, String::from("\n"), self.span_from(start)));
                 self.line += 1;
+                self.line_start = self.index;
 
             } else if char.is_ascii_whitespace() {
-                self.index += 1;
+                let start = self.index;
+                while self.index < chars_c && self.chars[self.index].is_ascii_whitespace() && self.chars[self.index] != '\n' {
+                    self.index += 1;
+                }
+                self.push_trivia(&mut tokens, TriviaKind::Whitespace, start);
 
             } else {
-                return Err(LexerError::new(lexer_error::LexerErrorKind::UnknownSymbol(self.chars[self.index]), self.line));
+                let start = self.index;
+                self.index += 1;
+                errors.push(LexerError::new(lexer_error::LexerErrorKind::UnknownSymbol(char), self.span_from(start)));
+                self.skip_word();
             }
 
         }
         
-        tokens.push(Token::eof_token(self.line));
-        Ok(tokens)
+        let eof_token = Token::eof_token(self.span_from(self.index));
+        self.push_token(&mut tokens, eof_token);
+        (tokens, errors)
     }
 }
 
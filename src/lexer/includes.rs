@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::lexer::Lexer;
+use crate::lexer::lexer_error::{LexerError, LexerErrorKind};
+use crate::lexer::token::{Token, TokenKind};
+use crate::sema::ast::helpers::str_lit_to_str;
+use crate::source_map::SourceMap;
+use crate::span::Span;
+
+
+
+struct IncludeExpander<'a> {
+    sources: &'a mut SourceMap,
+    include_paths: &'a [String],
+    max_errors: usize,
+    stack: Vec<(PathBuf, usize)>,
+    included: HashSet<PathBuf>,
+    errors: Vec<LexerError>
+}
+
+
+pub fn tokenise_with_includes(file: usize, sources: &mut SourceMap, include_paths: &[String], max_errors: usize) -> (Vec<Token>, Vec<LexerError>, bool) {
+    let limit = if max_errors == 0 { 0 } else { max_errors + 1 };
+    let mut expander = IncludeExpander { sources, include_paths, max_errors: limit, stack: Vec::new(), included: HashSet::new(), errors: Vec::new() };
+
+    let tokens = expander.expand(file);
+
+    let truncated = max_errors != 0 && expander.errors.len() > max_errors;
+    if truncated {
+        expander.errors.truncate(max_errors);
+    }
+
+    (tokens, expander.errors, truncated)
+}
+
+
+impl IncludeExpander<'_> {
+    fn expand(&mut self, file: usize) -> Vec<Token> {
+        let path = canonical(Path::new(&self.sources.file(file).name));
+        self.included.insert(path.clone());
+        self.stack.push((path, file));
+
+        let max_errors = if self.max_errors == 0 { 0 } else { self.max_errors - self.errors.len() };
+        let (tokens, errors) = Lexer::tokenise(&self.sources.file(file).source, file, max_errors);
+        self.errors.extend(errors);
+
+        let mut result = Vec::new();
+        let mut tokens = tokens.into_iter().peekable();
+
+        while let Some(token) = tokens.next() {
+            if token.kind != TokenKind::Directive || token.lexeme != ".include" {
+                result.push(token);
+                continue;
+            }
+
+            let Some(path_token) = tokens.next_if(|next| next.kind == TokenKind::String) else {
+                self.errors.push(LexerError::new(LexerErrorKind::ExpectedIncludePath, token.span));
+                continue;
+            };
+
+            if self.max_errors != 0 && self.errors.len() >= self.max_errors {
+                continue;
+            }
+
+            let name: String = str_lit_to_str(&path_token).into_iter().collect();
+            result.extend(self.include(&name, file, token.span.to(&path_token.span)));
+        }
+
+        self.stack.pop();
+        result
+    }
+
+    fn include(&mut self, name: &str, from: usize, span: Span) -> Vec<Token> {
+        let Some(path) = self.find(name, from) else {
+            self.errors.push(LexerError::new(LexerErrorKind::IncludeNotFound(name.to_string()), span));
+            return Vec::new();
+        };
+
+        let display = path.to_string_lossy().into_owned();
+        let canonical = canonical(&path);
+
+        if let Some(position) = self.stack.iter().position(|(other, _)| *other == canonical) {
+            let mut chain: Vec<&str> = self.stack[position..].iter().map(|(_, file)| self.sources.file(*file).name.as_str()).collect();
+            chain.push(&display);
+
+            self.errors.push(LexerError::new(LexerErrorKind::IncludeCycle(chain.join(" -> ")), span));
+            return Vec::new();
+        }
+
+        if self.included.contains(&canonical) {
+            return Vec::new();
+        }
+
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(e) => {
+                self.errors.push(LexerError::new(LexerErrorKind::UnreadableInclude(display, e.to_string()), span));
+                return Vec::new();
+            }
+        };
+
+        let file = self.sources.add(&display, source, Some(span));
+
+        let mut tokens = self.expand(file);
+
+        if let Some(eof) = tokens.pop() {
+            tokens.push(Token::new(TokenKind::Punctuation, String::from("\n"), eof.span));
+        }
+        tokens
+    }
+
+    fn find(&self, name: &str, from: usize) -> Option<PathBuf> {
+        let directory = Path::new(&self.sources.file(from).name).parent().map(Path::to_path_buf).unwrap_or_default();
+
+        std::iter::once(directory)
+            .chain(self.include_paths.iter().map(PathBuf::from))
+            .map(|directory| directory.join(name))
+            .find(|path| path.is_file())
+    }
+}
+
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -1,4 +1,5 @@
-
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::span::Span;
 
 
 pub enum LexerErrorKind {
@@ -8,32 +9,85 @@ pub enum LexerErrorKind {
     InvalidNumberLit(String),
     InvalidCharacterInString(char),
     UnterminatedString,
+    InvalidEscape(String),
+    InvalidCharLit(String),
+    NumberLitOverflow(String),
+    ExpectedIncludePath,
+    IncludeNotFound(String),
+    UnreadableInclude(String, String),
+    IncludeCycle(String),
 }
 
 pub struct LexerError {
     kind: LexerErrorKind,
-    line: u32,
+    span: Span,
 }
 
 impl LexerError {
     pub fn desc(&self) -> String {
-        format!("*** LEXER ERROR [LINE {}]: {}", &self.line, self.kind.desc())
+        format!("*** LEXER ERROR [LINE {}, COLUMN {}]: {}", self.span.line, self.span.column, self.kind.desc())
+    }
+
+    pub fn new(kind: LexerErrorKind, span: Span) -> Self {
+        LexerError { kind, span }
     }
 
-    pub fn new(kind: LexerErrorKind, line: u32) -> Self {
-        LexerError { kind, line }
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        let diagnostic = Diagnostic::error(self.kind.code(), &self.kind.desc(), Some(self.span));
+
+        match self.kind {
+            LexerErrorKind::InvalidNumberLit(_) => diagnostic.with_help("Number literals are written as #b, #o, #d or #x followed by digits of that radix, optionally separated by _."),
+            LexerErrorKind::InvalidEscape(_) => diagnostic.with_help("Valid escapes are \\n, \\t, \\0, \\\\, \\\" and \\x followed by two hex digits."),
+            LexerErrorKind::InvalidCharLit(_) => diagnostic.with_help("A character literal holds one character or escape below 256, such as 'A' or '\\n'."),
+            LexerErrorKind::NumberLitOverflow(_) => diagnostic.with_help("Number literals must lie between -2147483648 and 2147483647."),
+            LexerErrorKind::InvalidCharacterInString(_) => diagnostic.with_help("Strings hold printable characters below U+0100, one byte each; write others with \\x escapes."),
+            LexerErrorKind::UnterminatedString => diagnostic.with_help("Close the string with \" before the end of the line."),
+            LexerErrorKind::ExpectedIncludePath => diagnostic.with_help("Write the path as a string, such as .include \"macros.s\"."),
+            LexerErrorKind::IncludeNotFound(_) => diagnostic.with_help("Paths are searched relative to the including file first, then in each -I directory."),
+            LexerErrorKind::IncludeCycle(_) => diagnostic.with_note("A file cannot include itself, directly or through other files."),
+            _ => diagnostic
+        }
     }
 }
 
 impl LexerErrorKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownSymbol(_) => "E0101",
+            Self::InvalidMacro(_) => "E0102",
+            Self::InvalidDirective(_) => "E0103",
+            Self::InvalidNumberLit(_) => "E0104",
+            Self::InvalidCharacterInString(_) => "E0105",
+            Self::UnterminatedString => "E0106",
+            Self::InvalidEscape(_) => "E0107",
+            Self::InvalidCharLit(_) => "E0108",
+            Self::NumberLitOverflow(_) => "E0109",
+            Self::ExpectedIncludePath => "E0110",
+            Self::IncludeNotFound(_) => "E0111",
+            Self::UnreadableInclude(_, _) => "E0112",
+            Self::IncludeCycle(_) => "E0113"
+        }
+    }
+
     pub fn desc(&self) -> String {
         match self {
             Self::UnknownSymbol(c) => format!("Unknown character found: {c}"),
             Self::InvalidMacro(s) => format!("Invalid macro found: {s}"),
             Self::InvalidDirective(s) => format!("Invalid directive found: {s}"),
             Self::InvalidNumberLit(s) => format!("Invalid number literal found: {s}"),
-            Self::InvalidCharacterInString(c) => format!("Invalid character in a string found: {c}"),
-            Self::UnterminatedString => "Unterminated string found".to_string()
+            Self::InvalidCharacterInString(c) => format!("Invalid character in a string found: {}", c.escape_debug()),
+            Self::UnterminatedString => "Unterminated string found".to_string(),
+            Self::InvalidEscape(s) => format!("Invalid escape sequence in a string found: {s}"),
+            Self::InvalidCharLit(s) => format!("Invalid character literal found: {s}"),
+            Self::NumberLitOverflow(s) => format!("Number literal does not fit in 32 bits: {s}"),
+            Self::ExpectedIncludePath => "Expected a path string after .include".to_string(),
+            Self::IncludeNotFound(s) => format!("Included file not found: {s}"),
+            Self::UnreadableInclude(s, e) => format!("Could not read included file {s}: {e}"),
+            Self::IncludeCycle(s) => format!("Include cycle found: {s}")
         }
     }
 }
\ No newline at end of file
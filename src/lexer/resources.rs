@@ -63,15 +63,17 @@ pub static MACRO_NAMES: [&str; 3] = [
 	"!bl",
 ];
 
-pub static DIRECTIVE_NAMES: [&str; 8] = [
+pub static DIRECTIVE_NAMES: [&str; 10] = [
     ".res",
+	".equ",
 	".byte",
 	".bytes",
 	".arr",
 	".label",
 	".start",
 	".export",
-	".import"
+	".import",
+	".include"
 ];
 
 
@@ -1,4 +1,8 @@
 
+use crate::lexer::trivia::Trivia;
+use crate::span::Span;
+
+
 #[derive(Debug)]
 #[derive(PartialEq, Eq)]
 #[derive(Clone, Copy)]
@@ -13,6 +17,7 @@ pub enum TokenKind {
     ConditionCode,
     Identifier,
     Number,
+    Char,
     String,
     Punctuation,
     Eof
@@ -23,22 +28,24 @@ pub enum TokenKind {
 pub struct Token {
     pub kind: TokenKind,
     pub lexeme: String,
-    pub line: u32,
+    pub span: Span,
+    pub leading_trivia: Vec<Trivia>,
+    pub trailing_trivia: Vec<Trivia>
 }
 
 impl Token {
-    pub fn new(kind: TokenKind, lexeme: String, line: u32) -> Self {
-        Self  {kind: kind, lexeme: lexeme, line: line}
+    pub fn new(kind: TokenKind, lexeme: String, span: Span) -> Self {
+        Self { kind, lexeme, span, leading_trivia: Vec::new(), trailing_trivia: Vec::new() }
     }
 
-    pub fn eof_token(line: u32) -> Self {
-        Self { kind: TokenKind::Eof, lexeme: String::new(), line }
+    pub fn eof_token(span: Span) -> Self {
+        Self { kind: TokenKind::Eof, lexeme: String::new(), span, leading_trivia: Vec::new(), trailing_trivia: Vec::new() }
     }
 }
 
 impl std::fmt::Display for Token {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "line: {}, type: {:?}, lexeme: {}", self.line, self.kind, self.lexeme)
+        write!(f, "line: {}, column: {}, type: {:?}, lexeme: {}", self.span.line, self.span.column, self.kind, self.lexeme)
     }
 }
 
@@ -0,0 +1,18 @@
+use crate::span::Span;
+
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum TriviaKind {
+    Whitespace,
+    Comment
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+    pub span: Span
+}
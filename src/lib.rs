@@ -0,0 +1,13 @@
+pub mod span;
+pub mod source_map;
+pub mod diagnostics;
+pub mod lexer;
+pub mod parser;
+pub mod sema;
+pub mod codegen;
+pub mod object;
+pub mod linker;
+pub mod assembler;
+
+
+pub use assembler::{assemble, Artifact, Diagnostics, Options, Output, Stage};
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use linker_error::{LinkerError, LinkerErrorKind};
+
+use crate::codegen::layout::ADDRESS_SPACE_SIZE;
+use crate::object::object_file::{ObjectFile, RelocationKind, RelocationTarget};
+
+
+
+pub mod linker_error;
+pub mod intel_hex;
+
+
+pub struct LinkObject {
+    pub name: String,
+    pub object: ObjectFile
+}
+
+pub struct Image {
+    pub data: Vec<u8>,
+    pub entry: Option<u32>
+}
+
+
+pub fn link(objects: &[LinkObject]) -> Result<Image, Vec<LinkerError>> {
+    let mut errors = Vec::new();
+
+    let mut section_bases: Vec<Vec<u32>> = Vec::new();
+    let mut size: u32 = 0;
+
+    for link_object in objects {
+        let mut bases = Vec::new();
+        for section in &link_object.object.sections {
+            bases.push(size);
+            size = size.saturating_add(section.data.len() as u32);
+        }
+        section_bases.push(bases);
+    }
+
+    if size > ADDRESS_SPACE_SIZE {
+        return Err(vec![LinkerError::new(LinkerErrorKind::ImageTooLarge(size))]);
+    }
+
+    let mut exports: HashMap<&str, (u32, usize)> = HashMap::new();
+
+    for (object_i, link_object) in objects.iter().enumerate() {
+        for export in &link_object.object.exports {
+            let address = section_bases[object_i][export.section as usize] + export.offset;
+
+            if let Some((_, first_i)) = exports.get(export.name.as_str()) {
+                errors.push(LinkerError::new(LinkerErrorKind::DuplicateExport { name: export.name.clone(), first: objects[*first_i].name.clone(), second: link_object.name.clone() }));
+                continue;
+            }
+
+            exports.insert(&export.name, (address, object_i));
+        }
+    }
+
+    let mut entries = Vec::new();
+
+    for (object_i, link_object) in objects.iter().enumerate() {
+        if let Some(entry) = &link_object.object.entry {
+            entries.push((section_bases[object_i][entry.section as usize] + entry.offset, object_i));
+        }
+    }
+
+    if entries.len() > 1 {
+        errors.push(LinkerError::new(LinkerErrorKind::MultipleEntryPoints(entries.iter().map(|(_, i)| objects[*i].name.clone()).collect())));
+    }
+
+    let mut import_addresses: Vec<Vec<Option<u32>>> = Vec::new();
+
+    for link_object in objects {
+        let mut addresses = Vec::new();
+        for import in &link_object.object.imports {
+            let address = exports.get(import.name.as_str()).map(|(address, _)| *address);
+
+            if address.is_none() {
+                errors.push(LinkerError::new(LinkerErrorKind::UnresolvedImport { name: import.name.clone(), object: link_object.name.clone() }));
+            }
+            addresses.push(address);
+        }
+        import_addresses.push(addresses);
+    }
+
+    let mut data = Vec::with_capacity(size as usize);
+    for link_object in objects {
+        for section in &link_object.object.sections {
+            data.extend_from_slice(&section.data);
+        }
+    }
+
+    for (object_i, link_object) in objects.iter().enumerate() {
+        for relocation in &link_object.object.relocations {
+            let target = match relocation.target {
+                RelocationTarget::Section(index) => section_bases[object_i][index as usize],
+                RelocationTarget::Import(index) => match import_addresses[object_i][index as usize] {
+                    Some(address) => address,
+                    None => continue
+                }
+            };
+
+            let value = target as i64 + relocation.addend as i64;
+            let position = (section_bases[object_i][relocation.section as usize] + relocation.offset) as usize;
+
+            let Ok(value) = u16::try_from(value) else {
+                errors.push(LinkerError::new(LinkerErrorKind::AddressOutOfRange { object: link_object.name.clone(), offset: relocation.offset }));
+                continue;
+            };
+
+            match relocation.kind {
+                RelocationKind::Absolute16 => data[position..(position + 2)].copy_from_slice(&value.to_le_bytes()),
+                RelocationKind::Low8 => data[position] = value as u8,
+                RelocationKind::High8 => data[position] = (value >> 8) as u8
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(Image { data, entry: entries.first().map(|(address, _)| *address) })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::object_file::{Export, Import, Relocation, Section};
+
+    fn link_object(name: &str, object: ObjectFile) -> LinkObject {
+        LinkObject { name: name.to_string(), object }
+    }
+
+    fn empty_object() -> ObjectFile {
+        ObjectFile { sections: vec![Section { name: ".text".to_string(), data: vec![0; 4] }], exports: Vec::new(), imports: Vec::new(), relocations: Vec::new(), entry: None, line_table: None }
+    }
+
+    fn import_relocation(offset: u32, index: u16) -> Relocation {
+        Relocation { section: 0, offset, kind: RelocationKind::Absolute16, target: RelocationTarget::Import(index), addend: 0 }
+    }
+
+    fn error_descs(objects: &[LinkObject]) -> Vec<String> {
+        match link(objects) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors.iter().map(LinkerError::desc).collect()
+        }
+    }
+
+    #[test]
+    fn reports_unused_unresolved_imports() {
+        let object = ObjectFile { imports: vec![Import { name: "nope".to_string() }], ..empty_object() };
+
+        assert_eq!(error_descs(&[link_object("a.s", object)]), vec!["*** LINKER ERROR: Unresolved import nope in a.s"]);
+    }
+
+    #[test]
+    fn reports_each_unresolved_import_once() {
+        let object = ObjectFile { imports: vec![Import { name: "nope".to_string() }], relocations: vec![import_relocation(0, 0), import_relocation(2, 0)], ..empty_object() };
+
+        assert_eq!(error_descs(&[link_object("a.s", object)]).len(), 1);
+    }
+
+    #[test]
+    fn resolves_imports_against_other_objects() {
+        let user = ObjectFile { imports: vec![Import { name: "lib".to_string() }], relocations: vec![import_relocation(0, 0)], ..empty_object() };
+        let library = ObjectFile { exports: vec![Export { name: "lib".to_string(), section: 0, offset: 2 }], ..empty_object() };
+
+        let image = link(&[link_object("a.s", user), link_object("b.s", library)]).ok().unwrap();
+        assert_eq!(image.data, vec![0x06, 0x00, 0, 0, 0, 0, 0, 0]);
+    }
+}
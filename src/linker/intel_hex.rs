@@ -0,0 +1,32 @@
+use crate::linker::Image;
+
+
+
+pub fn write_intel_hex(image: &Image) -> String {
+    let mut out = String::new();
+
+    for (i, chunk) in image.data.chunks(16).enumerate() {
+        out.push_str(&record(0x00, (i * 16) as u16, chunk));
+    }
+
+    if let Some(entry) = image.entry {
+        out.push_str(&record(0x05, 0, &entry.to_be_bytes()));
+    }
+
+    out.push_str(&record(0x01, 0, &[]));
+    out
+}
+
+
+fn record(kind: u8, address: u16, data: &[u8]) -> String {
+    let mut bytes = vec![data.len() as u8];
+    bytes.extend_from_slice(&address.to_be_bytes());
+    bytes.push(kind);
+    bytes.extend_from_slice(data);
+
+    let checksum = bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)).wrapping_neg();
+    bytes.push(checksum);
+
+    let hex: String = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+    format!(":{}\n", hex)
+}
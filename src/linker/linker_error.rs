@@ -0,0 +1,58 @@
+
+
+use crate::diagnostics::diagnostic::Diagnostic;
+
+
+pub enum LinkerErrorKind {
+    UnresolvedImport { name: String, object: String },
+    DuplicateExport { name: String, first: String, second: String },
+    MultipleEntryPoints(Vec<String>),
+    AddressOutOfRange { object: String, offset: u32 },
+    ImageTooLarge(u32),
+}
+
+pub struct LinkerError {
+    kind: LinkerErrorKind,
+}
+
+impl LinkerError {
+    pub fn desc(&self) -> String {
+        format!("*** LINKER ERROR: {}", self.kind.desc())
+    }
+
+    pub fn new(kind: LinkerErrorKind) -> Self {
+        LinkerError { kind }
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        let diagnostic = Diagnostic::error(self.kind.code(), &self.kind.desc(), None);
+
+        match &self.kind {
+            LinkerErrorKind::UnresolvedImport { object, .. } | LinkerErrorKind::AddressOutOfRange { object, .. } => diagnostic.with_file(object),
+            LinkerErrorKind::DuplicateExport { second, .. } => diagnostic.with_file(second),
+            _ => diagnostic
+        }
+    }
+}
+
+impl LinkerErrorKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnresolvedImport { .. } => "E0501",
+            Self::DuplicateExport { .. } => "E0502",
+            Self::MultipleEntryPoints(_) => "E0503",
+            Self::AddressOutOfRange { .. } => "E0504",
+            Self::ImageTooLarge(_) => "E0505"
+        }
+    }
+
+    pub fn desc(&self) -> String {
+        match self {
+            Self::UnresolvedImport { name, object } => format!("Unresolved import {name} in {object}"),
+            Self::DuplicateExport { name, first, second } => format!("{name} is exported by both {first} and {second}"),
+            Self::MultipleEntryPoints(objects) => format!("Multiple entry points found in {}", objects.join(", ")),
+            Self::AddressOutOfRange { object, offset } => format!("Relocated address does not fit into 16 bits at offset {offset} in {object}"),
+            Self::ImageTooLarge(size) => format!("Linked image is {size} bytes, larger than the 64 KiB address space")
+        }
+    }
+}
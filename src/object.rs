@@ -0,0 +1,4 @@
+pub mod object_file;
+pub mod object_error;
+pub mod writer;
+pub mod reader;
@@ -0,0 +1,21 @@
+use crate::diagnostics::diagnostic::Diagnostic;
+
+
+pub struct ObjectError {
+    desc: String,
+}
+
+
+impl ObjectError {
+    pub fn desc(&self) -> String {
+        format!("*** OBJECT ERROR: {}", self.desc)
+    }
+
+    pub fn new(desc: &str) -> Self {
+        ObjectError { desc: desc.to_string() }
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        Diagnostic::error("E0601", &self.desc, None)
+    }
+}
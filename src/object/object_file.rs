@@ -0,0 +1,113 @@
+
+
+pub static OBJECT_MAGIC: [u8; 4] = *b"SRAO";
+pub const OBJECT_VERSION: u16 = 1;
+
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone)]
+pub struct Section {
+    pub name: String,
+    pub data: Vec<u8>
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone)]
+pub struct Export {
+    pub name: String,
+    pub section: u16,
+    pub offset: u32
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone)]
+pub struct Import {
+    pub name: String
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum RelocationKind {
+    Absolute16,
+    Low8,
+    High8
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum RelocationTarget {
+    Section(u16),
+    Import(u16)
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone)]
+pub struct Relocation {
+    pub section: u16,
+    pub offset: u32,
+    pub kind: RelocationKind,
+    pub target: RelocationTarget,
+    pub addend: i32
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone)]
+pub struct LineEntry {
+    pub section: u16,
+    pub offset: u32,
+    pub line: u32
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone)]
+pub struct EntryPoint {
+    pub section: u16,
+    pub offset: u32
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone)]
+pub struct ObjectFile {
+    pub sections: Vec<Section>,
+    pub exports: Vec<Export>,
+    pub imports: Vec<Import>,
+    pub relocations: Vec<Relocation>,
+    pub entry: Option<EntryPoint>,
+    pub line_table: Option<Vec<LineEntry>>
+}
+
+
+impl RelocationKind {
+    pub fn width(&self) -> u32 {
+        match self {
+            Self::Absolute16 => 2,
+            Self::Low8 | Self::High8 => 1
+        }
+    }
+
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::Absolute16 => 0,
+            Self::Low8 => 1,
+            Self::High8 => 2
+        }
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Self::Absolute16),
+            1 => Some(Self::Low8),
+            2 => Some(Self::High8),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use crate::object::object_error::ObjectError;
+use crate::object::object_file::{EntryPoint, Export, Import, LineEntry, ObjectFile, Relocation, RelocationKind, RelocationTarget, Section, OBJECT_MAGIC, OBJECT_VERSION};
+use crate::object::writer::{FLAG_ENTRY, FLAG_LINE_TABLE};
+
+
+
+pub fn read_object(bytes: &[u8]) -> Result<ObjectFile, ObjectError> {
+    let mut reader = Reader { bytes, index: 0 };
+
+    if reader.take(4)? != OBJECT_MAGIC {
+        return Err(ObjectError::new("Not an object file (invalid magic)."));
+    }
+
+    let version = reader.u16()?;
+    if version != OBJECT_VERSION {
+        return Err(ObjectError::new(format!("Unsupported object file version {}.", version).as_str()));
+    }
+
+    let flags = reader.u16()?;
+
+    let entry = if flags & FLAG_ENTRY != 0 {
+        Some(EntryPoint { section: reader.u16()?, offset: reader.u32()? })
+    } else {
+        None
+    };
+
+    let mut sections = Vec::new();
+    for _ in 0..reader.u16()? {
+        let name = reader.string()?;
+        let len = reader.u32()? as usize;
+        let data = reader.take(len)?.to_vec();
+        sections.push(Section { name, data });
+    }
+
+    let mut exports = Vec::new();
+    for _ in 0..reader.u32()? {
+        exports.push(Export { name: reader.string()?, section: reader.u16()?, offset: reader.u32()? });
+    }
+
+    let mut imports = Vec::new();
+    for _ in 0..reader.u32()? {
+        imports.push(Import { name: reader.string()? });
+    }
+
+    let mut relocations = Vec::new();
+    for _ in 0..reader.u32()? {
+        let section = reader.u16()?;
+        let offset = reader.u32()?;
+
+        let Some(kind) = RelocationKind::from_code(reader.u8()?) else {
+            return Err(ObjectError::new("Invalid relocation kind."));
+        };
+
+        let target = match (reader.u8()?, reader.u16()?) {
+            (0, index) => RelocationTarget::Section(index),
+            (1, index) => RelocationTarget::Import(index),
+            _ => return Err(ObjectError::new("Invalid relocation target."))
+        };
+
+        let addend = reader.u32()? as i32;
+
+        relocations.push(Relocation { section, offset, kind, target, addend });
+    }
+
+    let line_table = if flags & FLAG_LINE_TABLE != 0 {
+        let mut entries = Vec::new();
+        for _ in 0..reader.u32()? {
+            entries.push(LineEntry { section: reader.u16()?, offset: reader.u32()?, line: reader.u32()? });
+        }
+        Some(entries)
+    } else {
+        None
+    };
+
+    if reader.index != bytes.len() {
+        return Err(ObjectError::new("Trailing bytes after the end of the object file."));
+    }
+
+    let object = ObjectFile { sections, exports, imports, relocations, entry, line_table };
+    validate(&object)?;
+
+    Ok(object)
+}
+
+
+fn validate(object: &ObjectFile) -> Result<(), ObjectError> {
+    let section_len = |index: u16| object.sections.get(index as usize).map(|section| section.data.len() as u64);
+
+    if let Some(entry) = &object.entry && section_len(entry.section).is_none_or(|len| entry.offset as u64 > len) {
+        return Err(ObjectError::new("Entry point outside of its section."));
+    }
+
+    for export in &object.exports {
+        if section_len(export.section).is_none_or(|len| export.offset as u64 > len) {
+            return Err(ObjectError::new(format!("Export {} outside of its section.", export.name).as_str()));
+        }
+    }
+
+    for relocation in &object.relocations {
+        if section_len(relocation.section).is_none_or(|len| relocation.offset as u64 + relocation.kind.width() as u64 > len) {
+            return Err(ObjectError::new("Relocation outside of its section."));
+        }
+
+        let target_valid = match relocation.target {
+            RelocationTarget::Section(index) => (index as usize) < object.sections.len(),
+            RelocationTarget::Import(index) => (index as usize) < object.imports.len()
+        };
+
+        if !target_valid {
+            return Err(ObjectError::new("Relocation refers to a missing section or import."));
+        }
+    }
+
+    Ok(())
+}
+
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    index: usize
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ObjectError> {
+        if self.bytes.len() - self.index < len {
+            return Err(ObjectError::new("Unexpected end of object file."));
+        }
+
+        let slice = &self.bytes[self.index..(self.index + len)];
+        self.index += len;
+
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, ObjectError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ObjectError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, ObjectError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String, ObjectError> {
+        let len = self.u16()? as usize;
+
+        match String::from_utf8(self.take(len)?.to_vec()) {
+            Ok(string) => Ok(string),
+            Err(_) => Err(ObjectError::new("Invalid UTF-8 in a name."))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::writer::write_object;
+
+    fn sample_object() -> ObjectFile {
+        ObjectFile {
+            sections: vec![Section { name: ".text".to_string(), data: vec![0x03, 0x0F, 0x00, 0x00, 0xC0, 0x0F, 0x00, 0x01, 0x02, 0x00] }],
+            exports: vec![Export { name: "main".to_string(), section: 0, offset: 0 }, Export { name: "end".to_string(), section: 0, offset: 10 }],
+            imports: vec![Import { name: "putc".to_string() }, Import { name: "lib>buffer".to_string() }],
+            relocations: vec![
+                Relocation { section: 0, offset: 2, kind: RelocationKind::Absolute16, target: RelocationTarget::Import(0), addend: 0 },
+                Relocation { section: 0, offset: 7, kind: RelocationKind::Low8, target: RelocationTarget::Section(0), addend: 4 },
+                Relocation { section: 0, offset: 8, kind: RelocationKind::High8, target: RelocationTarget::Import(1), addend: -1 }
+            ],
+            entry: Some(EntryPoint { section: 0, offset: 4 }),
+            line_table: Some(vec![LineEntry { section: 0, offset: 0, line: 2 }, LineEntry { section: 0, offset: 4, line: 3 }, LineEntry { section: 0, offset: 7, line: 5 }])
+        }
+    }
+
+    fn read_error(bytes: &[u8]) -> String {
+        match read_object(bytes) {
+            Ok(object) => panic!("expected an error, read {:?}", object),
+            Err(e) => e.desc()
+        }
+    }
+
+    #[test]
+    fn round_trips_a_full_object() {
+        let object = sample_object();
+        assert_eq!(read_object(&write_object(&object)).ok(), Some(object));
+    }
+
+    #[test]
+    fn round_trips_an_object_without_entry_point_or_line_table() {
+        let object = ObjectFile { entry: None, line_table: None, ..sample_object() };
+        assert_eq!(read_object(&write_object(&object)).ok(), Some(object));
+    }
+
+    #[test]
+    fn round_trips_an_entry_point_at_the_end_of_its_section() {
+        let object = ObjectFile { entry: Some(EntryPoint { section: 0, offset: 10 }), ..sample_object() };
+        assert_eq!(read_object(&write_object(&object)).ok(), Some(object));
+    }
+
+    #[test]
+    fn round_trips_an_empty_object() {
+        let object = ObjectFile { sections: Vec::new(), exports: Vec::new(), imports: Vec::new(), relocations: Vec::new(), entry: None, line_table: Some(Vec::new()) };
+        assert_eq!(read_object(&write_object(&object)).ok(), Some(object));
+    }
+
+    #[test]
+    fn rejects_every_truncation() {
+        let bytes = write_object(&sample_object());
+
+        for len in 0..bytes.len() {
+            assert!(read_error(&bytes[..len]).contains("Unexpected end of object file."), "truncated to {len} bytes");
+        }
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let mut bytes = write_object(&sample_object());
+        bytes[0] = b'X';
+
+        assert!(read_error(&bytes).contains("invalid magic"));
+    }
+
+    #[test]
+    fn rejects_unsupported_version() {
+        let mut bytes = write_object(&sample_object());
+        bytes[4..6].copy_from_slice(&(OBJECT_VERSION + 1).to_le_bytes());
+
+        assert!(read_error(&bytes).contains(&format!("Unsupported object file version {}.", OBJECT_VERSION + 1)));
+    }
+
+    #[test]
+    fn rejects_trailing_bytes() {
+        let mut bytes = write_object(&sample_object());
+        bytes.push(0);
+
+        assert!(read_error(&bytes).contains("Trailing bytes"));
+    }
+
+    #[test]
+    fn rejects_relocations_outside_their_section() {
+        let mut object = sample_object();
+        object.relocations[0].offset = 9;
+
+        assert!(read_error(&write_object(&object)).contains("Relocation outside of its section."));
+    }
+
+    #[test]
+    fn rejects_entry_points_outside_their_section() {
+        let object = ObjectFile { entry: Some(EntryPoint { section: 0, offset: 11 }), ..sample_object() };
+
+        assert!(read_error(&write_object(&object)).contains("Entry point outside of its section."));
+    }
+}
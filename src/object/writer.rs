@@ -0,0 +1,90 @@
+use crate::object::object_file::{ObjectFile, RelocationTarget, OBJECT_MAGIC, OBJECT_VERSION};
+
+
+
+pub const FLAG_ENTRY: u16 = 0b01;
+pub const FLAG_LINE_TABLE: u16 = 0b10;
+
+
+pub fn write_object(object: &ObjectFile) -> Vec<u8> {
+    let mut writer = Writer { bytes: Vec::new() };
+
+    writer.bytes.extend_from_slice(&OBJECT_MAGIC);
+    writer.u16(OBJECT_VERSION);
+
+    let mut flags = 0;
+    if object.entry.is_some() {
+        flags |= FLAG_ENTRY;
+    }
+    if object.line_table.is_some() {
+        flags |= FLAG_LINE_TABLE;
+    }
+    writer.u16(flags);
+
+    if let Some(entry) = &object.entry {
+        writer.u16(entry.section);
+        writer.u32(entry.offset);
+    }
+
+    writer.u16(object.sections.len() as u16);
+    for section in &object.sections {
+        writer.string(&section.name);
+        writer.u32(section.data.len() as u32);
+        writer.bytes.extend_from_slice(&section.data);
+    }
+
+    writer.u32(object.exports.len() as u32);
+    for export in &object.exports {
+        writer.string(&export.name);
+        writer.u16(export.section);
+        writer.u32(export.offset);
+    }
+
+    writer.u32(object.imports.len() as u32);
+    for import in &object.imports {
+        writer.string(&import.name);
+    }
+
+    writer.u32(object.relocations.len() as u32);
+    for relocation in &object.relocations {
+        writer.u16(relocation.section);
+        writer.u32(relocation.offset);
+        writer.bytes.push(relocation.kind.code());
+        match relocation.target {
+            RelocationTarget::Section(index) => { writer.bytes.push(0); writer.u16(index); },
+            RelocationTarget::Import(index) => { writer.bytes.push(1); writer.u16(index); }
+        }
+        writer.u32(relocation.addend as u32);
+    }
+
+    if let Some(line_table) = &object.line_table {
+        writer.u32(line_table.len() as u32);
+        for entry in line_table {
+            writer.u16(entry.section);
+            writer.u32(entry.offset);
+            writer.u32(entry.line);
+        }
+    }
+
+    writer.bytes
+}
+
+
+struct Writer {
+    bytes: Vec<u8>
+}
+
+impl Writer {
+    fn u16(&mut self, value: u16) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn string(&mut self, value: &str) {
+        self.u16(value.len() as u16);
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+}
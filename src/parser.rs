@@ -1,8 +1,9 @@
 use core::panic;
 
-use crate::lexer::token::{self, Token, TokenKind};
+use crate::lexer::token::{Token, TokenKind};
+use crate::span::Span;
 use cst::{CstNode, CstNodeKind};
-use parser_error::ParserError;
+use parser_error::{ParserError, ParserErrorKind};
 use result::ParserResult;
 
 
@@ -12,17 +13,26 @@ pub mod result;
 
 
 
+static BINARY_OPERATORS: [&[&str]; 6] = [&["|"], &["^"], &["&"], &["<<", ">>"], &["+", "-"], &["*", "/", "%"]];
+
+pub const MAX_EXPRESSION_DEPTH: usize = 64;
+pub const MAX_EXPRESSION_OPERATORS: usize = 256;
+
+
 pub struct Parser<'a> {
     tokens: &'a [Token],
     index: usize,
-    line: u32
+    depth: usize,
+    operators: usize,
+    errors: Vec<ParserError>
 }
 
 impl Parser<'_> {
-    pub fn parse(tokens: &[Token]) -> ParserResult<CstNode, ParserError> {
-        let mut parser = Parser { tokens: tokens, index: 0, line: 1 };
+    pub fn parse(tokens: &[Token]) -> (CstNode, Vec<ParserError>) {
+        let mut parser = Parser { tokens, index: 0, depth: 0, operators: 0, errors: Vec::new() };
 
-        parser.parse_file()
+        let file = parser.parse_file();
+        (file, parser.errors)
     }
 }
 
@@ -36,9 +46,8 @@ impl<'a> Parser<'a> {
 
 
         if token.kind == kind {
-            self.line = token.line;
             self.index += 1;
-            Some(Token::new(kind, String::from(&token.lexeme), self.line))
+            Some(token.clone())
         } else {
             None
         }
@@ -52,9 +61,8 @@ impl<'a> Parser<'a> {
 
 
         if token.lexeme == lexeme {
-            self.line = token.line;
             self.index += 1;
-            Some(Token { kind: token.kind, lexeme: String::from(lexeme), line: self.line })
+            Some(token.clone())
         } else {
             None
         }
@@ -62,56 +70,117 @@ impl<'a> Parser<'a> {
 
 
 
+    fn pop_number_token(&mut self) -> Option<Token> {
+        if self.lookahead(0).is_some_and(|t| t.kind == TokenKind::Number && !t.lexeme.starts_with('#')) {
+            return None;
+        }
+
+        self.pop_token_if_kind(TokenKind::Number).or_else(|| self.pop_token_if_kind(TokenKind::Char))
+    }
+
+    fn pop_operator(&mut self, operator: &str) -> Option<Vec<Token>> {
+        let mut tokens: Vec<Token> = Vec::new();
+
+        for (i, c) in operator.chars().enumerate() {
+            let token = self.lookahead(i)?;
+
+            if token.kind != TokenKind::Punctuation || token.lexeme != c.to_string() || tokens.last().is_some_and(|previous| previous.span.end != token.span.start) {
+                return None;
+            }
+
+            tokens.push(token.clone());
+        }
+
+        self.index += tokens.len();
+        Some(tokens)
+    }
+
+
+
     fn lookahead(&self, k: usize) -> Option<&'a Token> {
         if self.index + k < self.tokens.len() { Some(&self.tokens[self.index + k]) } else { None }
     }
 
+    fn current_span(&self) -> Span {
+        match self.lookahead(0).or(self.tokens.last()) {
+            Some(token) => token.span,
+            None => Span::new(0, 0, 0, 1, 1)
+        }
+    }
 
 
-    fn parse_file(&mut self) -> ParserResult<CstNode, ParserError> {
-        let mut children: Vec<CstNode> = Vec::new();
 
-        let statements_node = self.parse_statements();
-        match statements_node {
-            ParserResult::Some(node) => children.push(node),
-            ParserResult::Err(error) => return ParserResult::Err(error),
-            ParserResult::None => panic!("INVALID"),
-        }
+    fn parse_file(&mut self) -> CstNode {
+        let mut children = vec![self.parse_statements()];
 
 
-        if self.pop_token_if_kind(TokenKind::Eof).is_some() {
-            ParserResult::Some(CstNode::nonterminal(CstNodeKind::File, children))
+        if let Some(token) = self.pop_token_if_kind(TokenKind::Eof) {
+            children.push(CstNode::terminal(token));
         } else {
-
-            ParserResult::Err(ParserError::new("Expected end of file", self.line))
+            self.errors.push(ParserError::new(ParserErrorKind::ExpectedStatement, "Expected end of file", self.current_span()));
         }
+
+        CstNode::nonterminal(CstNodeKind::File, children)
     }
 
 
-    fn parse_statements(&mut self) -> ParserResult<CstNode, ParserError> {
+    fn parse_statements(&mut self) -> CstNode {
         let mut children: Vec<CstNode> = Vec::new();
 
         loop {
+            while let Some(token) = self.pop_token_if_lexeme("\n") {
+                children.push(CstNode::terminal(token));
+            }
+
+            let start = self.index;
             let statement_node = self.parse_statement();
 
             match statement_node {
                 ParserResult::Some(node) => { children.push(node); },
-                ParserResult::None => { break; },
-                ParserResult::Err(_) => { return statement_node; },
+                ParserResult::None => {
+                    match self.lookahead(0) {
+                        Some(t) if t.kind != TokenKind::Eof => {
+                            self.errors.push(ParserError::new(ParserErrorKind::ExpectedStatement, format!("Expected a statement, not {:?} \"{}\".", t.kind, t.lexeme).as_str(), t.span));
+                            children.push(self.recover(start));
+                        },
+                        _ => { break; }
+                    }
+                },
+                ParserResult::Err(error) => {
+                    self.errors.push(error);
+                    children.push(self.recover(start));
+                },
             }
         }
 
-        ParserResult::Some(CstNode::nonterminal(CstNodeKind::Statements, children))
+        CstNode::nonterminal(CstNodeKind::Statements, children)
     }
 
 
-    fn parse_statement(&mut self) -> ParserResult<CstNode, ParserError> {
+    fn recover(&mut self, start: usize) -> CstNode {
         let mut children: Vec<CstNode> = Vec::new();
 
-        while self.pop_token_if_lexeme("\n").is_some() { }
+        self.index = start;
+
+        while let Some(t) = self.lookahead(0) && t.kind != TokenKind::Eof && t.lexeme != "\n" {
+            children.push(CstNode::terminal(t.clone()));
+            self.index += 1;
+        }
+
+        if let Some(token) = self.pop_token_if_lexeme("\n") {
+            children.push(CstNode::terminal(token));
+        }
+
+        CstNode::nonterminal(CstNodeKind::Error, children)
+    }
+
+
+    fn parse_statement(&mut self) -> ParserResult<CstNode, ParserError> {
+        let mut children: Vec<CstNode> = Vec::new();
 
 
         let child_node = self.parse_res_directive()
+            .or(|| self.parse_equ_directive())
             .or(|| self.parse_start_directive())
             .or(|| self.parse_import_directive())
             .or(|| self.parse_export_directive())
@@ -132,8 +201,10 @@ impl<'a> Parser<'a> {
         
         let new_line_token = self.pop_token_if_lexeme("\n");
         
-        if new_line_token.is_none() && let Some(t) = self.lookahead(0) && t.kind != TokenKind::Eof {
-            return ParserResult::Err(ParserError::new(format!("Expected a new line after a statement, not {:?} \"{}\"; {} unterminated.", t.kind, t.lexeme, child_node_desc).as_str(), self.line));
+        if let Some(token) = new_line_token {
+            children.push(CstNode::terminal(token));
+        } else if let Some(t) = self.lookahead(0) && t.kind != TokenKind::Eof {
+            return ParserResult::Err(ParserError::new(ParserErrorKind::UnterminatedStatement, format!("Expected a new line after a statement, not {:?} \"{}\"; {} unterminated.", t.kind, t.lexeme, child_node_desc).as_str(), self.current_span()));
         }
 
 
@@ -147,7 +218,7 @@ impl<'a> Parser<'a> {
     fn parse_label_definition(&mut self) -> ParserResult<CstNode, ParserError> {
         let mut children: Vec<CstNode> = Vec::new();
 
-        let mut auto_scope_prefix_count = 0;
+        let auto_scope_prefix_count;
         let auto_scope_prefix_node = self.parse_auto_scope_prefix();
         match auto_scope_prefix_node {
             ParserResult::Some(node) => { auto_scope_prefix_count = node.children.len(); children.push(node) },
@@ -161,7 +232,7 @@ impl<'a> Parser<'a> {
             if auto_scope_prefix_count == 0{
                 return ParserResult::None;
             }
-            return ParserResult::Err(ParserError::new("Expected identifier after auto scope prefix (>).", self.line))
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidLabel, "Expected identifier after auto scope prefix (>).", self.current_span()))
         }
 
         ParserResult::Some(CstNode::nonterminal(CstNodeKind::LabelDefinition, children))
@@ -207,7 +278,7 @@ impl<'a> Parser<'a> {
         if let Some(token) = self.pop_token_if_kind(TokenKind::Identifier) {
             children.push(CstNode::terminal(token));
         } else {
-            return ParserResult::Err(ParserError::new("Expected identifier after $ (label access).", self.line))
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidLabel, "Expected identifier after $ (label access).", self.current_span()))
         }
 
 
@@ -239,7 +310,7 @@ impl<'a> Parser<'a> {
         let mut children: Vec<CstNode> = Vec::new();
 
 
-        if !(self.lookahead(1).is_some_and(|t| t.lexeme == ">")) {
+        if self.lookahead(1).is_none_or(|t| t.lexeme != ">") || self.lookahead(2).is_none_or(|t| t.kind != TokenKind::Identifier) {
             return ParserResult::None;
         }
 
@@ -247,13 +318,13 @@ impl<'a> Parser<'a> {
         if let Some(token) = self.pop_token_if_kind(TokenKind::Identifier) {
             children.push(CstNode::terminal(token));
         } else {
-            return ParserResult::Err(ParserError::new("Expected an identifier before >.", self.line))
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidLabel, "Expected an identifier before >.", self.current_span()))
         }
 
         if let Some(token) = self.pop_token_if_lexeme( ">") {
             children.push(CstNode::terminal(token));
         } else {
-            return ParserResult::Err(ParserError::new("NEVER", self.line))
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidLabel, "NEVER", self.current_span()))
         }
 
 
@@ -284,13 +355,13 @@ impl<'a> Parser<'a> {
         if let Some(token) = self.pop_token_if_kind(TokenKind::Identifier) {
             children.push(CstNode::terminal(token));
         } else {
-            return ParserResult::Err(ParserError::new("Expected identifier in an external label expression.", self.line))
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidLabel, "Expected identifier in an external label expression.", self.current_span()))
         }
 
         if let Some(token) = self.pop_token_if_lexeme(")") {
             children.push(CstNode::terminal(token));
         } else {
-            return ParserResult::Err(ParserError::new("Expected ) after an external label expression.", self.line))
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidLabel, "Expected ) after an external label expression.", self.current_span()))
         }
 
 
@@ -311,14 +382,14 @@ impl<'a> Parser<'a> {
         let label_definition_node = self.parse_label_definition();
         match label_definition_node {
             ParserResult::Some(node) => { children.push(node) },
-            ParserResult::None => { return ParserResult::Err(ParserError::new("Expected label definition after .res directive", self.line)) },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected label definition after .res directive", self.current_span())) },
             ParserResult::Err(_) => { return label_definition_node }
         }
 
         let type_directive_node = self.parse_type_directive();
         match type_directive_node {
             ParserResult::Some(node) => { children.push(node); },
-            ParserResult::None => { return ParserResult::Err(ParserError::new("Expected type in .res directive.", self.line)); },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected type in .res directive.", self.current_span())); },
             ParserResult::Err(_) => { return type_directive_node; },
         }
 
@@ -335,6 +406,33 @@ impl<'a> Parser<'a> {
     }
 
 
+    fn parse_equ_directive(&mut self) -> ParserResult<CstNode, ParserError> {
+        let mut children: Vec<CstNode> = Vec::new();
+
+        if let Some(token) = self.pop_token_if_lexeme(".equ") {
+            children.push(CstNode::terminal(token));
+        } else {
+            return ParserResult::None;
+        }
+
+        let label_definition_node = self.parse_label_definition();
+        match label_definition_node {
+            ParserResult::Some(node) => { children.push(node) },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected constant name after .equ directive", self.current_span())) },
+            ParserResult::Err(_) => { return label_definition_node }
+        }
+
+        let number_node = self.parse_number();
+        match number_node {
+            ParserResult::Some(node) => { children.push(node); },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected a number or an expression after the constant name in .equ directive", self.current_span())); },
+            ParserResult::Err(_) => { return number_node; },
+        }
+
+        ParserResult::Some(CstNode::nonterminal(CstNodeKind::EquDirective, children))
+    }
+
+
     fn parse_type_directive(&mut self) -> ParserResult<CstNode, ParserError> {
         let mut children: Vec<CstNode> = Vec::new();
 
@@ -378,10 +476,11 @@ impl<'a> Parser<'a> {
             return ParserResult::None;
         }
 
-        if let Some(token) = self.pop_token_if_kind(TokenKind::Number) {
-            children.push(CstNode::terminal(token));
-        } else {
-            return ParserResult::Err(ParserError::new("Expected a number (element size) after a .bytes directive", self.line));
+        let number_node = self.parse_number();
+        match number_node {
+            ParserResult::Some(node) => { children.push(node); },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected a number (element size) after a .bytes directive", self.current_span())); },
+            ParserResult::Err(_) => { return number_node; },
         }
 
         ParserResult::Some(CstNode::nonterminal(CstNodeKind::BytesDirective, children))
@@ -398,16 +497,17 @@ impl<'a> Parser<'a> {
             return ParserResult::None;
         }
 
-        if let Some(token) = self.pop_token_if_kind(TokenKind::Number) {
-            children.push(CstNode::terminal(token));
-        } else {
-            return ParserResult::Err(ParserError::new("Expected a number (array size) after an .arr directive", self.line));
+        let number_node = self.parse_number();
+        match number_node {
+            ParserResult::Some(node) => { children.push(node); },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected a number (array size) after an .arr directive", self.current_span())); },
+            ParserResult::Err(_) => { return number_node; },
         }
 
         let type_directive_node = self.parse_type_directive();
         match type_directive_node {
             ParserResult::Some(node) => { children.push(node); },
-            ParserResult::None => { return ParserResult::Err(ParserError::new("Expected type in .arr directive.", self.line)); },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected type in .arr directive.", self.current_span())); },
             ParserResult::Err(_) => { return type_directive_node; },
         }
 
@@ -436,7 +536,7 @@ impl<'a> Parser<'a> {
         if let Some(token) = self.pop_token_if_lexeme( "}") {
             children.push(CstNode::terminal(token));
         } else {
-            return ParserResult::Err(ParserError::new("Assignment list must be terminated with }.", self.line));
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Assignment list must be terminated with }.", self.current_span()));
         }
 
 
@@ -463,9 +563,12 @@ impl<'a> Parser<'a> {
             return ParserResult::None;
         }
 
-        if let Some(token) = self.pop_token_if_kind(TokenKind::Number) {
-            children.push(CstNode::terminal(token));
-        } 
+        let number_node = self.parse_number();
+        match number_node {
+            ParserResult::Some(node) => { children.push(node); },
+            ParserResult::None => {  },
+            ParserResult::Err(_) => { return number_node; },
+        }
 
         ParserResult::Some(CstNode::nonterminal(CstNodeKind::AssignmentRepetition, children)) 
 
@@ -501,13 +604,20 @@ impl<'a> Parser<'a> {
             ParserResult::Err(_) => { return assignment_node; },
         }
         
+        if children.is_empty() {
+            let number_node = self.parse_number();
+            match number_node {
+                ParserResult::Some(node) => { children.push(node); },
+                ParserResult::None => {  },
+                ParserResult::Err(_) => { return number_node; },
+            }
+        }
+
         if !children.is_empty() {
             return ParserResult::Some(CstNode::nonterminal(CstNodeKind::AssignmentValue, children))
         }
 
-        if let Some(token) = self.pop_token_if_kind(TokenKind::Number) {
-            children.push(CstNode::terminal(token));
-        } else if let Some(token) = self.pop_token_if_kind(TokenKind::String) {
+        if let Some(token) = self.pop_token_if_kind(TokenKind::String) {
             children.push(CstNode::terminal(token));
         } else {
             return ParserResult::None
@@ -519,6 +629,201 @@ impl<'a> Parser<'a> {
     }
 
 
+    fn parse_number(&mut self) -> ParserResult<CstNode, ParserError> {
+        if let Some(token) = self.pop_number_token() {
+            return ParserResult::Some(CstNode::terminal(token));
+        }
+
+        self.parse_expression()
+    }
+
+
+    fn parse_expression(&mut self) -> ParserResult<CstNode, ParserError> {
+        let mut children: Vec<CstNode> = Vec::new();
+
+        if let Some(token) = self.pop_token_if_lexeme("#") {
+            children.push(CstNode::terminal(token));
+        } else {
+            return ParserResult::None;
+        }
+
+        if let Some(token) = self.pop_token_if_lexeme("(") {
+            children.push(CstNode::terminal(token));
+        } else {
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidExpression, "Expected ( after # in an expression.", self.current_span()));
+        }
+
+        self.depth = 0;
+        self.operators = 0;
+
+        let expression_node = self.parse_binary_expression(0);
+        match expression_node {
+            ParserResult::Some(node) => { children.push(node); },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidExpression, "Expected an expression after #(.", self.current_span())); },
+            ParserResult::Err(_) => { return expression_node; },
+        }
+
+        if let Some(token) = self.pop_token_if_lexeme(")") {
+            children.push(CstNode::terminal(token));
+        } else {
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidExpression, "Expected an operator or ) in an expression.", self.current_span()));
+        }
+
+        ParserResult::Some(CstNode::nonterminal(CstNodeKind::Expression, children))
+    }
+
+
+    fn parse_binary_expression(&mut self, level: usize) -> ParserResult<CstNode, ParserError> {
+        if level == BINARY_OPERATORS.len() {
+            return self.parse_unary_expression();
+        }
+
+        let mut lhs_node = match self.parse_binary_expression(level + 1) {
+            ParserResult::Some(node) => node,
+            other => { return other; }
+        };
+
+        while let Some((operator, tokens)) = BINARY_OPERATORS[level].iter().find_map(|operator| self.pop_operator(operator).map(|tokens| (operator, tokens))) {
+            if self.operators == MAX_EXPRESSION_OPERATORS {
+                return ParserResult::Err(ParserError::new(ParserErrorKind::ExpressionTooLong, format!("Expression has more than {} binary operators.", MAX_EXPRESSION_OPERATORS).as_str(), tokens[0].span));
+            }
+            self.operators += 1;
+
+            let rhs_node = match self.parse_binary_expression(level + 1) {
+                ParserResult::Some(node) => node,
+                ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidExpression, format!("Expected an operand after {}.", operator).as_str(), self.current_span())); },
+                other => { return other; }
+            };
+
+            let mut children = vec![lhs_node];
+            children.extend(tokens.into_iter().map(CstNode::terminal));
+            children.push(rhs_node);
+
+            lhs_node = CstNode::nonterminal(CstNodeKind::BinaryExpression, children);
+        }
+
+        ParserResult::Some(lhs_node)
+    }
+
+
+    fn enter_expression(&mut self) -> Option<ParserResult<CstNode, ParserError>> {
+        if self.depth == MAX_EXPRESSION_DEPTH {
+            return Some(ParserResult::Err(ParserError::new(ParserErrorKind::ExpressionTooDeep, format!("Expression nested more than {} levels deep.", MAX_EXPRESSION_DEPTH).as_str(), self.current_span())));
+        }
+
+        self.depth += 1;
+        None
+    }
+
+
+    fn parse_unary_expression(&mut self) -> ParserResult<CstNode, ParserError> {
+        if let Some(error) = self.enter_expression() {
+            return error;
+        }
+
+        let node = self.parse_unary_operand();
+
+        self.depth -= 1;
+        node
+    }
+
+
+    fn parse_unary_operand(&mut self) -> ParserResult<CstNode, ParserError> {
+        let mut children: Vec<CstNode> = Vec::new();
+
+        if let Some(token) = self.pop_token_if_lexeme("-").or_else(|| self.pop_token_if_lexeme("~")) {
+            children.push(CstNode::terminal(token));
+        } else {
+            return self.parse_primary_expression();
+        }
+
+        let operand_node = self.parse_unary_expression();
+        match operand_node {
+            ParserResult::Some(node) => { children.push(node); },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidExpression, format!("Expected an operand after {}.", children[0].terminal.as_ref().unwrap().lexeme).as_str(), self.current_span())); },
+            ParserResult::Err(_) => { return operand_node; },
+        }
+
+        ParserResult::Some(CstNode::nonterminal(CstNodeKind::UnaryExpression, children))
+    }
+
+
+    fn parse_primary_expression(&mut self) -> ParserResult<CstNode, ParserError> {
+        let mut children: Vec<CstNode> = Vec::new();
+
+        if let Some(token) = self.pop_token_if_kind(TokenKind::Number).or_else(|| self.pop_token_if_kind(TokenKind::Char)) {
+            return ParserResult::Some(CstNode::terminal(token));
+        }
+
+        let label_access_node = self.parse_label_access();
+        match label_access_node {
+            ParserResult::None => {  },
+            _ => { return label_access_node; }
+        }
+
+        if self.lookahead(0).is_some_and(|t| t.kind == TokenKind::Identifier) && self.lookahead(1).is_some_and(|t| t.lexeme == "(") {
+            return self.parse_function_expression();
+        }
+
+        if let Some(token) = self.pop_token_if_kind(TokenKind::Identifier) {
+            return ParserResult::Some(CstNode::nonterminal(CstNodeKind::NameExpression, vec![CstNode::terminal(token)]));
+        }
+
+        if let Some(token) = self.pop_token_if_lexeme("(") {
+            children.push(CstNode::terminal(token));
+        } else {
+            return ParserResult::None;
+        }
+
+        let expression_node = self.parse_binary_expression(0);
+        match expression_node {
+            ParserResult::Some(node) => { children.push(node); },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidExpression, "Expected an expression after (.", self.current_span())); },
+            ParserResult::Err(_) => { return expression_node; },
+        }
+
+        if let Some(token) = self.pop_token_if_lexeme(")") {
+            children.push(CstNode::terminal(token));
+        } else {
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidExpression, "Expected an operator or ) in an expression.", self.current_span()));
+        }
+
+        ParserResult::Some(CstNode::nonterminal(CstNodeKind::ParenthesizedExpression, children))
+    }
+
+
+    fn parse_function_expression(&mut self) -> ParserResult<CstNode, ParserError> {
+        let mut children: Vec<CstNode> = Vec::new();
+
+        if let Some(token) = self.pop_token_if_lexeme("lo").or_else(|| self.pop_token_if_lexeme("hi")) {
+            children.push(CstNode::terminal(token));
+        } else {
+            return ParserResult::Err(ParserError::new(ParserErrorKind::UnknownFunction, format!("Unknown function {}, expected lo or hi.", self.lookahead(0).unwrap().lexeme).as_str(), self.current_span()));
+        }
+
+        if let Some(token) = self.pop_token_if_lexeme("(") {
+            children.push(CstNode::terminal(token));
+        } else {
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidExpression, "Expected ( after a function name.", self.current_span()));
+        }
+
+        let expression_node = self.parse_binary_expression(0);
+        match expression_node {
+            ParserResult::Some(node) => { children.push(node); },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidExpression, format!("Expected an expression after {}(.", children[0].terminal.as_ref().unwrap().lexeme).as_str(), self.current_span())); },
+            ParserResult::Err(_) => { return expression_node; },
+        }
+
+        if let Some(token) = self.pop_token_if_lexeme(")") {
+            children.push(CstNode::terminal(token));
+        } else {
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidExpression, "Expected an operator or ) in an expression.", self.current_span()));
+        }
+
+        ParserResult::Some(CstNode::nonterminal(CstNodeKind::FunctionExpression, children))
+    }
+
+
     fn parse_start_directive(&mut self) -> ParserResult<CstNode, ParserError> {
         let mut children: Vec<CstNode> = Vec::new();
 
@@ -531,7 +836,7 @@ impl<'a> Parser<'a> {
         if let Some(token) = self.pop_token_if_lexeme( ":") {
             children.push(CstNode::terminal(token));
         } else {
-            return ParserResult::Err(ParserError::new("Expected : after a .start directive.", self.line))
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected : after a .start directive.", self.current_span()))
         }
 
         ParserResult::Some(CstNode::nonterminal(CstNodeKind::StartDirective, children)) 
@@ -552,14 +857,14 @@ impl<'a> Parser<'a> {
         let label_definition_node = self.parse_label_definition();
         match label_definition_node {
             ParserResult::Some(node) => { children.push(node) },
-            ParserResult::None => { return ParserResult::Err(ParserError::new("Expected label definition after .import directive", self.line)) },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected label definition after .import directive", self.current_span())) },
             ParserResult::Err(_) => { return label_definition_node }
         }
 
         let label_external_node = self.parse_label_external();
         match label_external_node {
             ParserResult::Some(node) => { children.push(node) },
-            ParserResult::None => { return ParserResult::Err(ParserError::new("Expected external label in .import directive", self.line)) },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected external label in .import directive", self.current_span())) },
             ParserResult::Err(_) => { return label_external_node }
         }
 
@@ -581,14 +886,14 @@ impl<'a> Parser<'a> {
         let label_access_node = self.parse_label_access();
         match label_access_node {
             ParserResult::Some(node) => { children.push(node) },
-            ParserResult::None => { return ParserResult::Err(ParserError::new("Expected label access after .export directive", self.line)) },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected label access after .export directive", self.current_span())) },
             ParserResult::Err(_) => { return label_access_node }
         }
 
         let label_external_node = self.parse_label_external();
         match label_external_node {
             ParserResult::Some(node) => { children.push(node) },
-            ParserResult::None => { return ParserResult::Err(ParserError::new("Expected external label in .export directive", self.line)) },
+            ParserResult::None => { return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidDirective, "Expected external label in .export directive", self.current_span())) },
             ParserResult::Err(_) => { return label_external_node }
         }
 
@@ -611,7 +916,7 @@ impl<'a> Parser<'a> {
         if let Some(token) = self.pop_token_if_lexeme( ":") {
             children.push(CstNode::terminal(token));
         } else {
-            return ParserResult::Err(ParserError::new("Expected : after a label directive.", self.line))
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidLabel, "Expected : after a label directive.", self.current_span()))
         }
 
         ParserResult::Some(CstNode::nonterminal(CstNodeKind::LabelDirective, children)) 
@@ -669,7 +974,7 @@ impl<'a> Parser<'a> {
         if let Some(token) = self.pop_token_if_kind(TokenKind::ConditionCode) {
             children.push(CstNode::terminal(token));
         } else {
-            return ParserResult::Err(ParserError::new("Expected condition code after : in an instruction.", self.line));
+            return ParserResult::Err(ParserError::new(ParserErrorKind::InvalidInstruction, "Expected condition code after : in an instruction.", self.current_span()));
         }
 
         ParserResult::Some(CstNode::nonterminal(CstNodeKind::ConditionCode, children)) 
@@ -698,6 +1003,13 @@ impl<'a> Parser<'a> {
     fn parse_instruction_argument(&mut self) -> ParserResult<CstNode, ParserError> {
         let mut children: Vec<CstNode> = Vec::new();
 
+        let number_node = self.parse_number();
+        match number_node {
+            ParserResult::Some(node) => { children.push(node); },
+            ParserResult::None => {  },
+            ParserResult::Err(_) => { return number_node; },
+        }
+
         if !children.is_empty() {
             return ParserResult::Some(CstNode::nonterminal(CstNodeKind::InstructionArgument, children)); 
         }
@@ -708,8 +1020,6 @@ impl<'a> Parser<'a> {
             children.push(CstNode::terminal(token));
         } else if let Some(token) = self.pop_token_if_kind(TokenKind::Port) {
             children.push(CstNode::terminal(token));
-        } else if let Some(token) = self.pop_token_if_kind(TokenKind::Number) {
-            children.push(CstNode::terminal(token));
         } else if let Some(token) = self.pop_token_if_kind(TokenKind::String) {
             children.push(CstNode::terminal(token));
         } else if let Some(token) = self.pop_token_if_kind(TokenKind::LongRegister) {
@@ -786,14 +1096,21 @@ impl<'a> Parser<'a> {
             ParserResult::Err(_) => { return label_access_node }
         }
 
-        if children.len() != 0 {
+        if children.is_empty() {
+            let number_node = self.parse_number();
+            match number_node {
+                ParserResult::Some(node) => { children.push(node); },
+                ParserResult::None => {  },
+                ParserResult::Err(_) => { return number_node; },
+            }
+        }
+
+        if !children.is_empty() {
             return ParserResult::Some(CstNode::nonterminal(CstNodeKind::MacroArgument, children))
         }
 
         if let Some(token) = self.pop_token_if_kind(TokenKind::Register) {
             children.push(CstNode::terminal(token));
-        } else if let Some(token) = self.pop_token_if_kind(TokenKind::Number) {
-            children.push(CstNode::terminal(token));
         } else if let Some(token) = self.pop_token_if_kind(TokenKind::LongRegister) {
             children.push(CstNode::terminal(token));
         } else {
@@ -812,3 +1129,41 @@ impl<'a> Parser<'a> {
 
 
 
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+
+    fn error_count(expression: &str) -> usize {
+        let (tokens, errors) = Lexer::tokenise(&format!("main:\n  mov r1 #({})\n", expression), 0, 0);
+        assert!(errors.is_empty());
+
+        Parser::parse(&tokens).1.len()
+    }
+
+    #[test]
+    fn accepts_expressions_up_to_the_depth_limit() {
+        let depth = MAX_EXPRESSION_DEPTH - 1;
+
+        assert_eq!(error_count(&format!("{}1{}", "(".repeat(depth), ")".repeat(depth))), 0);
+        assert_eq!(error_count(&format!("{}1", "~".repeat(depth))), 0);
+    }
+
+    #[test]
+    fn accepts_flat_operator_chains_up_to_the_operator_limit() {
+        assert_eq!(error_count(&vec!["1"; MAX_EXPRESSION_DEPTH + 1].join("+")), 0);
+        assert_eq!(error_count(&vec!["1"; MAX_EXPRESSION_OPERATORS + 1].join("+")), 0);
+        assert_eq!(error_count(&vec!["1"; MAX_EXPRESSION_OPERATORS + 2].join("+")), 1);
+    }
+
+    #[test]
+    fn rejects_deeply_nested_expressions() {
+        let depth = 100_000;
+
+        assert_eq!(error_count(&format!("{}1{}", "(".repeat(depth), ")".repeat(depth))), 1);
+        assert_eq!(error_count(&format!("{}1", "~".repeat(depth))), 1);
+        assert_eq!(error_count(&vec!["1"; depth].join("+")), 1);
+    }
+}
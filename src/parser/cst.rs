@@ -1,5 +1,6 @@
 
 use crate::lexer::token;
+use crate::span::Span;
 
 #[derive(Debug)]
 #[derive(PartialEq)]
@@ -10,6 +11,7 @@ pub enum CstNodeKind {
     Statements,
     Statement,
     ResDirective,
+    EquDirective,
     ImportDirective,
     ExportDirective,
     TypeDirective,
@@ -35,27 +37,44 @@ pub enum CstNodeKind {
     Macro,
     MacroArguments,
     MacroArgument,
+    Expression,
+    BinaryExpression,
+    UnaryExpression,
+    ParenthesizedExpression,
+    FunctionExpression,
+    NameExpression,
+    Error,
 }
 #[derive(Debug)]
 pub struct CstNode {
     pub kind: CstNodeKind,
     pub children: Vec<CstNode>,
-    pub terminal: Option<token::Token>
+    pub terminal: Option<token::Token>,
+    pub span: Option<Span>
 }
 
 impl CstNode {
     pub fn nonterminal(kind: CstNodeKind, children: Vec<CstNode>) -> CstNode {
-        CstNode { kind, children, terminal: None }
+        let first = children.iter().find_map(|child| child.span);
+        let last = children.iter().rev().find_map(|child| child.span);
+        let span = match (first, last) {
+            (Some(first), Some(last)) => Some(first.to(&last)),
+            _ => None
+        };
+
+        CstNode { kind, children, terminal: None, span }
     }
 
     pub fn terminal(terminal: token::Token) -> CstNode {
-        CstNode { kind: CstNodeKind::Terminal, children: Vec::new(), terminal: Some(terminal) }
+        let span = Some(terminal.span);
+        CstNode { kind: CstNodeKind::Terminal, children: Vec::new(), terminal: Some(terminal), span }
     }
 
     pub fn kind_desc(&self) -> String {
         match self.kind {
             CstNodeKind::Instruction => "instruction statement".to_string(),
             CstNodeKind::ResDirective => "reserve directive".to_string(),
+            CstNodeKind::EquDirective => "constant directive".to_string(),
             CstNodeKind::ImportDirective => "import directive".to_string(),
             CstNodeKind::ExportDirective => "export directive".to_string(),
             CstNodeKind::StartDirective => "start directive".to_string(),
@@ -65,12 +84,34 @@ impl CstNode {
         }
     }
 
-    pub fn child<'a>(&'a self, index: usize) -> &'a CstNode {
+    pub fn to_source(&self) -> String {
+        let mut source = String::new();
+        self.write_source(&mut source);
+        source
+    }
+
+    fn write_source(&self, source: &mut String) {
+        if let Some(token) = &self.terminal {
+            for trivia in &token.leading_trivia {
+                source.push_str(&trivia.text);
+            }
+            source.push_str(&token.lexeme);
+            for trivia in &token.trailing_trivia {
+                source.push_str(&trivia.text);
+            }
+        }
+
+        for child in &self.children {
+            child.write_source(source);
+        }
+    }
+
+    pub fn child(&self, index: usize) -> &CstNode {
         
         &self.children[index]
     }
 
-    pub fn child_mut<'a>(&'a mut self, index: usize) -> &'a mut CstNode {
+    pub fn child_mut(&mut self, index: usize) -> &mut CstNode {
         
         &mut self.children[index]
     }
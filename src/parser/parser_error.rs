@@ -1,22 +1,57 @@
-
-
-
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::span::Span;
+
+
+pub enum ParserErrorKind {
+    ExpectedStatement,
+    UnterminatedStatement,
+    InvalidLabel,
+    InvalidDirective,
+    InvalidInstruction,
+    InvalidExpression,
+    UnknownFunction,
+    ExpressionTooDeep,
+    ExpressionTooLong,
+}
 
 pub struct ParserError {
+    kind: ParserErrorKind,
     desc: String,
-    line: u32,
+    span: Span,
 }
 
 
 impl ParserError {
     pub fn desc(&self) -> String {
-        format!("*** PARSER ERROR [LINE {}]: {}", self.line, self.desc)   
+        format!("*** PARSER ERROR [LINE {}, COLUMN {}]: {}", self.span.line, self.span.column, self.desc)   
        
     }
 
-    pub fn new(desc: &str, line: u32) -> Self {
-        ParserError { desc: desc.to_string(), line }
+    pub fn new(kind: ParserErrorKind, desc: &str, span: Span) -> Self {
+        ParserError { kind, desc: desc.to_string(), span }
     }
-}
 
+    pub fn span(&self) -> Span {
+        self.span
+    }
 
+    pub fn diagnostic(&self) -> Diagnostic {
+        Diagnostic::error(self.kind.code(), &self.desc, Some(self.span))
+    }
+}
+
+impl ParserErrorKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ExpectedStatement => "E0201",
+            Self::UnterminatedStatement => "E0202",
+            Self::InvalidLabel => "E0203",
+            Self::InvalidDirective => "E0204",
+            Self::InvalidInstruction => "E0205",
+            Self::InvalidExpression => "E0206",
+            Self::UnknownFunction => "E0207",
+            Self::ExpressionTooDeep => "E0208",
+            Self::ExpressionTooLong => "E0209"
+        }
+    }
+}
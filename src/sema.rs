@@ -1,4 +1,15 @@
 pub mod label_expander;
 pub mod sema_error;
+pub mod sema_warning;
+pub mod symbol_table;
+pub mod macro_expander;
+pub mod operand_checker;
+pub mod immediate_checker;
+pub mod size_checker;
+pub mod entry_checker;
+pub mod expression_evaluator;
+pub mod constant_expander;
+pub mod label_checker;
+pub mod reachability_checker;
 pub mod ast;
 
@@ -1,14 +1,12 @@
-use crate::{parser::cst::{CstNode, CstNodeKind}};
-
-
-
 pub mod statement;
 pub mod res_directive;
+pub mod equ_directive;
 pub mod export_directive;
 pub mod import_directive;
 pub mod instruction;
 pub mod r#macro;
 pub mod label_directive;
+pub mod start_directive;
 pub mod file;
 pub mod data_type;
 pub mod assignment;
@@ -16,6 +14,7 @@ pub mod labels;
 pub mod helpers;
 pub mod macro_arg;
 pub mod instruction_arg;
+pub mod expression;
 
 
 
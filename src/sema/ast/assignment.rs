@@ -1,33 +1,64 @@
-use crate::{lexer::token::TokenKind, parser::cst::{CstNode, CstNodeKind}, sema::ast::helpers::{num_lit_to_int, str_lit_to_str}};
+use crate::{lexer::token::TokenKind, parser::cst::{CstNode, CstNodeKind}, span::Span, sema::ast::{expression::Expression, helpers::str_lit_to_str}};
 
 
 #[derive(Debug)]
 pub enum AssignmentValue {
-    Number(i32),
+    Number(Expression),
     String(Vec<char>),
     Assignment(Assignment)
 }
 #[derive(Debug)]
 pub struct Assignment {
     pub values: Vec<AssignmentValue>,
-    pub repetition: u32
+    pub repetition: Expression,
+    pub span: Span
 }
 
 impl Assignment {
     pub fn from(node: &CstNode) -> Self {
         assert_eq!(node.kind, CstNodeKind::Assignment);
 
+        let span = node.span.unwrap();
+
         let assignment_values_node = node.child(1);
         let values = make_values(assignment_values_node);
 
         if node.children.len() == 3 {
-            return Self { values, repetition: 1 };
+            return Self { values, repetition: Expression::number(1, span), span };
         }
 
         let repetition_node = node.child(3);
         let repetition = get_repetition(repetition_node);
 
-        Self { values, repetition }
+        Self { values, repetition, span }
+    }
+
+    pub fn expressions(&self) -> Vec<&Expression> {
+        let mut expressions = vec![&self.repetition];
+
+        for value in &self.values {
+            match value {
+                AssignmentValue::Number(expression) => expressions.push(expression),
+                AssignmentValue::Assignment(inner) => expressions.extend(inner.expressions()),
+                AssignmentValue::String(_) => {}
+            }
+        }
+
+        expressions
+    }
+
+    pub fn expressions_mut(&mut self) -> Vec<&mut Expression> {
+        let mut expressions = vec![&mut self.repetition];
+
+        for value in &mut self.values {
+            match value {
+                AssignmentValue::Number(expression) => expressions.push(expression),
+                AssignmentValue::Assignment(inner) => expressions.extend(inner.expressions_mut()),
+                AssignmentValue::String(_) => {}
+            }
+        }
+
+        expressions
     }
 }
 
@@ -43,13 +74,13 @@ fn make_values(node: &CstNode) -> Vec<AssignmentValue> {
 
 }
 
-fn get_repetition(node: &CstNode) -> u32 {
+fn get_repetition(node: &CstNode) -> Expression {
     assert_eq!(node.kind, CstNodeKind::AssignmentRepetition);
 
     if node.children.len() == 1 {
-        0
+        Expression::number(0, node.span.unwrap())
     } else {
-        num_lit_to_int(node.child(1).terminal.as_ref().unwrap()) as u32
+        Expression::from(node.child(1))
     }
 }
 
@@ -58,8 +89,8 @@ fn get_value(node: &CstNode) -> AssignmentValue {
 
     if node.child(0).kind == CstNodeKind::Assignment {
         AssignmentValue::Assignment(Assignment::from(node.child(0)))
-    } else if node.child(0).terminal.as_ref().unwrap().kind == TokenKind::Number {
-        AssignmentValue::Number(num_lit_to_int(node.child(0).terminal.as_ref().unwrap()))
+    } else if node.child(0).kind == CstNodeKind::Expression || node.child(0).terminal.as_ref().unwrap().kind != TokenKind::String {
+        AssignmentValue::Number(Expression::from(node.child(0)))
     } else {
         AssignmentValue::String(str_lit_to_str(node.child(0).terminal.as_ref().unwrap()))
     }
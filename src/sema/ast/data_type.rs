@@ -1,13 +1,11 @@
-use core::num;
-
-use crate::{parser::cst::{CstNode, CstNodeKind}, sema::ast::helpers::num_lit_to_int};
+use crate::{parser::cst::{CstNode, CstNodeKind}, sema::ast::expression::Expression};
 
 
 #[derive(Debug)]
 pub enum DataType {
     Byte,
-    Bytes(u32),
-    Arr(u32, Box<DataType>)
+    Bytes(Expression),
+    Arr(Expression, Box<DataType>)
 }
 
 
@@ -17,27 +15,54 @@ impl DataType {
         let kind = node.child(0).kind;
 
         match kind {
-            CstNodeKind::ByteDirective => { return Self::Byte; },
-            CstNodeKind::BytesDirective => { return make_bytes_type(node.child(0)); },
-            CstNodeKind::ArrDirective => { return make_arr_type(node.child(0)); },
+            CstNodeKind::ByteDirective => Self::Byte,
+            CstNodeKind::BytesDirective => make_bytes_type(node.child(0)),
+            CstNodeKind::ArrDirective => make_arr_type(node.child(0)),
             _ => unreachable!()
         }
     }
+
+    pub fn size(&self) -> u32 {
+        match self {
+            Self::Byte => 1,
+            Self::Bytes(n) => count(n),
+            Self::Arr(n, data_type) => count(n).saturating_mul(data_type.size())
+        }
+    }
+
+    pub fn expressions(&self) -> Vec<&Expression> {
+        match self {
+            Self::Byte => Vec::new(),
+            Self::Bytes(n) => vec![n],
+            Self::Arr(n, data_type) => std::iter::once(n).chain(data_type.expressions()).collect()
+        }
+    }
+
+    pub fn expressions_mut(&mut self) -> Vec<&mut Expression> {
+        match self {
+            Self::Byte => Vec::new(),
+            Self::Bytes(n) => vec![n],
+            Self::Arr(n, data_type) => std::iter::once(n).chain(data_type.expressions_mut()).collect()
+        }
+    }
+}
+
+
+fn count(expression: &Expression) -> u32 {
+    expression.value().unwrap() as u32
 }
 
 
 fn make_bytes_type(node: &CstNode) -> DataType{
     assert_eq!(node.kind, CstNodeKind::BytesDirective);
 
-    let number_token = node.child(1).terminal.as_ref().unwrap();
-    DataType::Bytes(num_lit_to_int(number_token) as u32)
+    DataType::Bytes(Expression::from(node.child(1)))
 }
 
 fn make_arr_type(node: &CstNode) -> DataType{
     assert_eq!(node.kind, CstNodeKind::ArrDirective);
 
-    let number_token = node.child(1).terminal.as_ref().unwrap();
-    let num = num_lit_to_int(number_token) as u32;
+    let num = Expression::from(node.child(1));
 
     let type_node = node.child(2);
     let data_type = DataType::from(type_node);
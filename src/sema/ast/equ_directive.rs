@@ -0,0 +1,24 @@
+use crate::{parser::cst::{CstNode, CstNodeKind}, span::Span, sema::ast::{expression::Expression, labels::LabelDefinition}};
+
+
+#[derive(Debug)]
+pub struct EquDirective {
+    pub label: LabelDefinition,
+    pub value: Expression,
+    pub span: Span
+}
+
+impl EquDirective {
+    pub fn from(node: &CstNode) -> Self {
+        assert_eq!(node.kind, CstNodeKind::EquDirective);
+
+        let span = node.span.unwrap();
+
+        let label_definition_node = node.child(1);
+        let label = LabelDefinition::from(label_definition_node);
+
+        let value = Expression::from(node.child(2));
+
+        Self { label, value, span }
+    }
+}
@@ -1,22 +1,23 @@
-use crate::{parser::cst::{CstNode, CstNodeKind}, sema::ast::labels::{LabelAccess, LabelExternal}};
+use crate::{parser::cst::{CstNode, CstNodeKind}, span::Span, sema::ast::labels::{LabelAccess, LabelExternal}};
 
 
+#[derive(Debug)]
 pub struct ExportDirective {
     pub label_intern: LabelAccess,
     pub label_extern: LabelExternal,
-    pub line: u32
+    pub span: Span
 }
 
 impl ExportDirective {
     pub fn from(node: &CstNode) -> Self {
         assert_eq!(node.kind, CstNodeKind::ExportDirective);
 
-        let line = node.child(0).terminal.as_ref().unwrap().line;
+        let span = node.span.unwrap();
 
         let label_access_node = node.child(1);
         let label_external_node = node.child(2);
 
-        Self { label_intern: LabelAccess::from(label_access_node), label_extern: LabelExternal::from(label_external_node), line }
+        Self { label_intern: LabelAccess::from(label_access_node), label_extern: LabelExternal::from(label_external_node), span }
 
     }
 }
\ No newline at end of file
@@ -0,0 +1,187 @@
+use crate::{lexer::token::TokenKind, parser::cst::{CstNode, CstNodeKind}, span::Span, sema::ast::{helpers::num_lit_to_int, labels::LabelAccess}};
+
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum UnaryOperator {
+    Negate,
+    Not,
+    Low,
+    High
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    ShiftLeft,
+    ShiftRight,
+    And,
+    Or,
+    Xor
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub enum ExpressionKind {
+    Number(i32),
+    Label(LabelAccess),
+    Unary(UnaryOperator, Box<Expression>),
+    Binary(BinaryOperator, Box<Expression>, Box<Expression>)
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub span: Span
+}
+
+
+impl UnaryOperator {
+    pub fn from(lexeme: &str) -> Self {
+        match lexeme {
+            "-" => Self::Negate,
+            "~" => Self::Not,
+            "lo" => Self::Low,
+            "hi" => Self::High,
+            _ => unreachable!()
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Negate => "-",
+            Self::Not => "~",
+            Self::Low => "lo",
+            Self::High => "hi"
+        }
+    }
+}
+
+impl BinaryOperator {
+    pub fn from(lexeme: &str) -> Self {
+        match lexeme {
+            "+" => Self::Add,
+            "-" => Self::Subtract,
+            "*" => Self::Multiply,
+            "/" => Self::Divide,
+            "%" => Self::Remainder,
+            "<<" => Self::ShiftLeft,
+            ">>" => Self::ShiftRight,
+            "&" => Self::And,
+            "|" => Self::Or,
+            "^" => Self::Xor,
+            _ => unreachable!()
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Remainder => "%",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
+            Self::And => "&",
+            Self::Or => "|",
+            Self::Xor => "^"
+        }
+    }
+}
+
+
+impl Expression {
+    pub fn number(value: i32, span: Span) -> Self {
+        Self { kind: ExpressionKind::Number(value), span }
+    }
+
+    pub fn depth(&self) -> usize {
+        match &self.kind {
+            ExpressionKind::Number(_) | ExpressionKind::Label(_) => 1,
+            ExpressionKind::Unary(_, operand) => operand.depth() + 1,
+            ExpressionKind::Binary(_, lhs, rhs) => lhs.depth().max(rhs.depth()) + 1
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match &self.kind {
+            ExpressionKind::Number(_) | ExpressionKind::Label(_) => 1,
+            ExpressionKind::Unary(_, operand) => operand.size() + 1,
+            ExpressionKind::Binary(_, lhs, rhs) => lhs.size() + rhs.size() + 1
+        }
+    }
+
+    pub fn from(node: &CstNode) -> Self {
+        let span = node.span.unwrap();
+
+        match node.kind {
+            CstNodeKind::Terminal => {
+                let token = node.terminal.as_ref().unwrap();
+                assert!(token.kind == TokenKind::Number || token.kind == TokenKind::Char);
+
+                Self::number(num_lit_to_int(token), span)
+            },
+            CstNodeKind::Expression => Self { span, ..Self::from(node.child(2)) },
+            CstNodeKind::ParenthesizedExpression => Self { span, ..Self::from(node.child(1)) },
+            CstNodeKind::LabelAccess => Self { kind: ExpressionKind::Label(LabelAccess::from(node)), span },
+            CstNodeKind::NameExpression => Self { kind: ExpressionKind::Label(LabelAccess::from_name(node)), span },
+            CstNodeKind::FunctionExpression => {
+                let operator = UnaryOperator::from(&node.child(0).terminal.as_ref().unwrap().lexeme);
+                let operand = Self::from(node.child(2));
+
+                Self { kind: ExpressionKind::Unary(operator, Box::new(operand)), span }
+            },
+            CstNodeKind::UnaryExpression => {
+                let operator = UnaryOperator::from(&node.child(0).terminal.as_ref().unwrap().lexeme);
+                let operand = Self::from(node.child(1));
+
+                Self { kind: ExpressionKind::Unary(operator, Box::new(operand)), span }
+            },
+            CstNodeKind::BinaryExpression => {
+                let last = node.children.len() - 1;
+                let lexeme: String = node.children[1..last].iter().map(|child| child.terminal.as_ref().unwrap().lexeme.as_str()).collect();
+
+                let operator = BinaryOperator::from(&lexeme);
+                let lhs = Self::from(node.child(0));
+                let rhs = Self::from(node.child(last));
+
+                Self { kind: ExpressionKind::Binary(operator, Box::new(lhs), Box::new(rhs)), span }
+            },
+            _ => unreachable!()
+        }
+    }
+
+    pub fn value(&self) -> Option<i32> {
+        match self.kind {
+            ExpressionKind::Number(n) => Some(n),
+            _ => None
+        }
+    }
+
+    pub fn labels(&self) -> Vec<&LabelAccess> {
+        match &self.kind {
+            ExpressionKind::Number(_) => Vec::new(),
+            ExpressionKind::Label(label) => vec![label],
+            ExpressionKind::Unary(_, operand) => operand.labels(),
+            ExpressionKind::Binary(_, lhs, rhs) => lhs.labels().into_iter().chain(rhs.labels()).collect()
+        }
+    }
+
+    pub fn labels_mut(&mut self) -> Vec<&mut LabelAccess> {
+        match &mut self.kind {
+            ExpressionKind::Number(_) => Vec::new(),
+            ExpressionKind::Label(label) => vec![label],
+            ExpressionKind::Unary(_, operand) => operand.labels_mut(),
+            ExpressionKind::Binary(_, lhs, rhs) => lhs.labels_mut().into_iter().chain(rhs.labels_mut()).collect()
+        }
+    }
+}
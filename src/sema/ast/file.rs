@@ -2,6 +2,7 @@ use crate::{parser::cst::{CstNode, CstNodeKind}, sema::{ast::statement::Statemen
 
 
 
+#[derive(Debug)]
 pub struct File {
     pub statements: Vec<Statement>
 }
@@ -17,6 +18,9 @@ impl File {
 
 
         for statement_node in statement_nodes {
+            if statement_node.kind != CstNodeKind::Statement {
+                continue;
+            }
             statements.push(Statement::from(statement_node));
         }
 
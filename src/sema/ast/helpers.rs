@@ -1,24 +1,45 @@
+use crate::lexer::Lexer;
 use crate::lexer::token::{Token, TokenKind};
 
 
 
 pub fn num_lit_to_int(token: &Token) -> i32 {
+    if token.kind == TokenKind::Char {
+        return unescape(&token.lexeme[1..(token.lexeme.len()-1)])[0] as i32;
+    }
+
     assert_eq!(token.kind, TokenKind::Number);
 
-    let radix = token.lexeme.chars().nth(1).unwrap();
-    print!("{radix}");
-    let string = &token.lexeme[2..];
-    match radix {
-        'b' => i32::from_str_radix(string, 2),
-        'o' => i32::from_str_radix(string, 8),
-        'd' => i32::from_str_radix(string, 10),
-        'x' => i32::from_str_radix(string, 16),
-        _ => unreachable!(),
-    }.unwrap()
+    Lexer::number_lit_value(&token.lexeme).unwrap()
 }
 
 pub fn str_lit_to_str(token: &Token) -> Vec<char> {
     assert_eq!(token.kind, TokenKind::String);
 
-    token.lexeme[1..(token.lexeme.len()-1)].chars().collect()
+    unescape(&token.lexeme[1..(token.lexeme.len()-1)])
+}
+
+fn unescape(text: &str) -> Vec<char> {
+    let mut result = Vec::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next().unwrap() {
+            'n' => result.push('\n'),
+            't' => result.push('\t'),
+            '0' => result.push('\0'),
+            'x' => {
+                let digits: String = chars.by_ref().take(2).collect();
+                result.push(char::from(u8::from_str_radix(&digits, 16).unwrap()));
+            },
+            escaped => result.push(escaped)
+        }
+    }
+
+    result
 }
\ No newline at end of file
@@ -1,22 +1,23 @@
-use crate::{parser::cst::{CstNode, CstNodeKind}, sema::ast::labels::{LabelDefinition, LabelExternal}};
+use crate::{parser::cst::{CstNode, CstNodeKind}, span::Span, sema::ast::labels::{LabelDefinition, LabelExternal}};
 
 
+#[derive(Debug)]
 pub struct ImportDirective {
     pub label_intern: LabelDefinition,
     pub label_extern: LabelExternal,
-    pub line: u32
+    pub span: Span
 }
 
 impl ImportDirective {
     pub fn from(node: &CstNode) -> Self {
         assert_eq!(node.kind, CstNodeKind::ImportDirective);
 
-        let line = node.child(0).terminal.as_ref().unwrap().line;
+        let span = node.span.unwrap();
 
         let label_definition_node = node.child(1);
         let label_external_node = node.child(2);
 
-        Self { label_intern: LabelDefinition::from(label_definition_node), label_extern: LabelExternal::from(label_external_node), line }
+        Self { label_intern: LabelDefinition::from(label_definition_node), label_extern: LabelExternal::from(label_external_node), span }
 
     }
 }
\ No newline at end of file
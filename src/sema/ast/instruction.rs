@@ -1,22 +1,24 @@
 
 
-use crate::{parser::cst::{CstNode, CstNodeKind}, sema::ast::instruction_arg::InstructionArg};
+use crate::{parser::cst::{CstNode, CstNodeKind}, span::Span, sema::ast::instruction_arg::InstructionArg};
 
 
 
 
+#[derive(Debug)]
 pub struct Instruction {
     pub mnemonic: String,
     pub condition: Option<String>,
     pub args: Vec<InstructionArg>,
-    pub line: u32
+    pub arg_spans: Vec<Span>,
+    pub span: Span
 }
 
 impl Instruction {
     pub fn from(node: &CstNode) -> Self {
         assert_eq!(node.kind, CstNodeKind::Instruction);
 
-        let line = node.child(0).terminal.as_ref().unwrap().line;
+        let span = node.span.unwrap();
 
         let mnemonic = node.child(0).terminal.as_ref().unwrap().lexeme.clone();
 
@@ -29,12 +31,14 @@ impl Instruction {
         }
 
         let mut args = Vec::new();
+        let mut arg_spans = Vec::new();
 
         for arg_node in &node.child(args_i).children {
             args.push(InstructionArg::from(arg_node));
+            arg_spans.push(arg_node.span.unwrap());
         }
 
 
-        Self { mnemonic, condition: condition_code, args, line }
+        Self { mnemonic, condition: condition_code, args, arg_spans, span }
     }
 }
\ No newline at end of file
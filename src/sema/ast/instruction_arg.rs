@@ -1,4 +1,4 @@
-use crate::{lexer::token::TokenKind, parser::cst::{CstNode, CstNodeKind}, sema::ast::helpers::{num_lit_to_int, str_lit_to_str}};
+use crate::{lexer::token::TokenKind, parser::cst::{CstNode, CstNodeKind}, sema::ast::{expression::Expression, helpers::str_lit_to_str, labels::LabelAccess}};
 
 
 #[derive(Debug)]
@@ -6,25 +6,51 @@ pub enum InstructionArg {
     Register(String),
     SystemRegister(String),
     Port(String),
-    Number(i32),
+    Number(Expression),
     String(Vec<char>),
-    LongRegister(String)
+    LongRegister(String),
+    Label(LabelAccess)
 }
 
 impl InstructionArg {
     pub fn from(node: &CstNode) -> Self {
         assert_eq!(node.kind, CstNodeKind::InstructionArgument);
 
+        if node.child(0).kind == CstNodeKind::Expression {
+            return Self::Number(Expression::from(node.child(0)));
+        }
+
         let token = node.child(0).terminal.as_ref().unwrap();
 
         match token.kind {
             TokenKind::Register => Self::Register(token.lexeme.clone()),
             TokenKind::SystemRegister => Self::SystemRegister(token.lexeme.clone()),
             TokenKind::Port => Self::Port(token.lexeme.clone()),
-            TokenKind::Number => Self::Number(num_lit_to_int(token)),
+            TokenKind::Number | TokenKind::Char => Self::Number(Expression::from(node.child(0))),
             TokenKind::String => Self::String(str_lit_to_str(token)),
             TokenKind::LongRegister => Self::LongRegister(token.lexeme.clone()),
             _ => unreachable!()
         }
     }
+
+    pub fn immediate_value(&self) -> Option<i32> {
+        match self {
+            Self::Number(n) => n.value(),
+            Self::String(s) if s.len() == 1 => Some(s[0] as i32),
+            _ => None
+        }
+    }
+
+    pub fn desc(&self) -> String {
+        match self {
+            Self::Register(_) => "<register>".to_string(),
+            Self::SystemRegister(_) => "<system register>".to_string(),
+            Self::Port(_) => "<port>".to_string(),
+            Self::Number(_) => "<immediate>".to_string(),
+            Self::String(s) if s.len() == 1 => "<immediate>".to_string(),
+            Self::String(_) => "<string>".to_string(),
+            Self::LongRegister(_) => "<long register>".to_string(),
+            Self::Label(_) => "<label>".to_string()
+        }
+    }
 }
\ No newline at end of file
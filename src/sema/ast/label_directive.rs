@@ -1,20 +1,21 @@
-use crate::{parser::cst::{CstNode, CstNodeKind}, sema::ast::labels::LabelDefinition};
+use crate::{parser::cst::{CstNode, CstNodeKind}, span::Span, sema::ast::labels::LabelDefinition};
 
 
+#[derive(Debug)]
 pub struct LabelDirective {
     pub label: LabelDefinition,
-    pub line: u32
+    pub span: Span
 }
 
 impl LabelDirective {
     pub fn from(node: &CstNode) -> Self {
         assert_eq!(node.kind, CstNodeKind::LabelDirective);
 
-        let line = node.child(1).terminal.as_ref().unwrap().line;
+        let span = node.span.unwrap();
 
         let label_definition_node = node.child(0);
         let label_definition = LabelDefinition::from(label_definition_node);
 
-        Self { label: label_definition, line }
+        Self { label: label_definition, span }
     }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use crate::parser::cst::{CstNode, CstNodeKind};
+use crate::span::Span;
 
 
 #[derive(Debug)]
@@ -6,7 +7,8 @@ pub struct LabelDefinition {
     pub prefix_count: u32,
     pub label: String,
 
-    pub str: Option<String>
+    pub str: Option<String>,
+    pub span: Span
 }
 
 impl LabelDefinition {
@@ -19,16 +21,18 @@ impl LabelDefinition {
         let identifier_token = node.child(1).terminal.as_ref().unwrap();
         let label = identifier_token.lexeme.clone();
 
-        Self { prefix_count, label, str: None }
+        Self { prefix_count, label, str: None, span: node.span.unwrap() }
     }
 }
 #[derive(Debug)]
+#[derive(Clone)]
 pub struct LabelAccess {
     pub prefix_count: u32,
     pub scopes: Vec<String>,
     pub label: String,
 
-    pub str: Option<String>
+    pub str: Option<String>,
+    pub span: Span
 }
 
 impl LabelAccess {
@@ -48,7 +52,15 @@ impl LabelAccess {
         let identifier_token = node.child(3).terminal.as_ref().unwrap();
         let label = identifier_token.lexeme.clone();
 
-        Self { prefix_count, scopes, label, str: None }
+        Self { prefix_count, scopes, label, str: None, span: node.span.unwrap() }
+    }
+
+    pub fn from_name(node: &CstNode) -> Self {
+        assert_eq!(node.kind, CstNodeKind::NameExpression);
+
+        let label = node.child(0).terminal.as_ref().unwrap().lexeme.clone();
+
+        Self { prefix_count: 0, scopes: Vec::new(), label, str: None, span: node.span.unwrap() }
     }
 }
 #[derive(Debug)]
@@ -56,7 +68,8 @@ pub struct LabelExternal {
     pub scopes: Vec<String>,
     pub label: String,
 
-    pub str: Option<String>
+    pub str: Option<String>,
+    pub span: Span
 }
 
 impl LabelExternal {
@@ -73,6 +86,6 @@ impl LabelExternal {
         let identifier_token = node.child(2).terminal.as_ref().unwrap();
         let label = identifier_token.lexeme.clone();
 
-        Self { scopes, label, str: None }
+        Self { scopes, label, str: None, span: node.span.unwrap() }
     }
 }
\ No newline at end of file
@@ -1,23 +1,25 @@
 
 
 
-use crate::{parser::cst::{CstNode, CstNodeKind}, sema::ast::{instruction_arg::InstructionArg, macro_arg::MacroArg}};
+use crate::{parser::cst::{CstNode, CstNodeKind}, span::Span, sema::ast::macro_arg::MacroArg};
 
 
 
 
+#[derive(Debug)]
 pub struct Macro {
     pub mnemonic: String,
     pub condition: Option<String>,
     pub args: Vec<MacroArg>,
-    pub line: u32
+    pub arg_spans: Vec<Span>,
+    pub span: Span
 }
 
 impl Macro {
     pub fn from(node: &CstNode) -> Self {
         assert_eq!(node.kind, CstNodeKind::Macro);
 
-        let line = node.child(0).terminal.as_ref().unwrap().line;
+        let span = node.span.unwrap();
 
         let mnemonic = node.child(0).terminal.as_ref().unwrap().lexeme.clone();
 
@@ -30,12 +32,14 @@ impl Macro {
         }
 
         let mut args = Vec::new();
+        let mut arg_spans = Vec::new();
 
         for arg_node in &node.child(args_i).children {
             args.push(MacroArg::from(arg_node));
+            arg_spans.push(arg_node.span.unwrap());
         }
 
 
-        Self { mnemonic, condition: condition_code, args, line }
+        Self { mnemonic, condition: condition_code, args, arg_spans, span }
     }
 }
\ No newline at end of file
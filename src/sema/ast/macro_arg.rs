@@ -1,10 +1,10 @@
-use crate::{lexer::token::TokenKind, parser::cst::{CstNode, CstNodeKind}, sema::ast::{helpers::{num_lit_to_int, str_lit_to_str}, labels::LabelAccess}};
+use crate::{lexer::token::TokenKind, parser::cst::{CstNode, CstNodeKind}, sema::ast::{expression::Expression, labels::LabelAccess}};
 
 
 #[derive(Debug)]
 pub enum MacroArg {
     Register(String),
-    Number(i32),
+    Number(Expression),
     LongRegister(String),
     Label(LabelAccess)
 }
@@ -18,13 +18,26 @@ impl MacroArg {
             return Self::Label(LabelAccess::from(node.child(0)));
         }
 
+        if node.child(0).kind == CstNodeKind::Expression {
+            return Self::Number(Expression::from(node.child(0)));
+        }
+
         let token = node.child(0).terminal.as_ref().unwrap();
 
         match token.kind {
             TokenKind::Register => Self::Register(token.lexeme.clone()),
-            TokenKind::Number => Self::Number(num_lit_to_int(token)),
+            TokenKind::Number | TokenKind::Char => Self::Number(Expression::from(node.child(0))),
             TokenKind::LongRegister => Self::LongRegister(token.lexeme.clone()),
             _ => unreachable!()
         }
     }
+
+    pub fn desc(&self) -> String {
+        match self {
+            Self::Register(_) => "<register>".to_string(),
+            Self::Number(_) => "<immediate>".to_string(),
+            Self::LongRegister(_) => "<long register>".to_string(),
+            Self::Label(_) => "<label>".to_string()
+        }
+    }
 }
\ No newline at end of file
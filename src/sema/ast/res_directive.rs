@@ -1,16 +1,17 @@
-use crate::{parser::cst::CstNode, sema::{ast::{assignment::{Assignment}, data_type::DataType, labels::LabelDefinition}}};
+use crate::{parser::cst::CstNode, span::Span, sema::{ast::{assignment::{Assignment}, data_type::DataType, labels::LabelDefinition}}};
 
+#[derive(Debug)]
 pub struct ResDirective {
     pub label: LabelDefinition,
     pub data_type: DataType,
     pub assignment: Option<Assignment>,
-    pub line: u32
+    pub span: Span
 }
 
 impl ResDirective {
     pub fn from(node: &CstNode) -> Self {
 
-        let line = node.child(0).terminal.as_ref().unwrap().line;
+        let span = node.span.unwrap();
 
         let label_definition_node = node.child(1);
         let label = LabelDefinition::from(label_definition_node);
@@ -19,12 +20,12 @@ impl ResDirective {
         let data_type = DataType::from(data_type_node);
 
         if node.children.len() == 3 {
-            return ResDirective { label, data_type, assignment: None , line: 1};
+            return ResDirective { label, data_type, assignment: None, span };
         }
 
         let assignment_node = node.child(3);
         let assignment = Assignment::from(assignment_node);
 
-        ResDirective { label, data_type, assignment: Some(assignment), line }
+        ResDirective { label, data_type, assignment: Some(assignment), span }
     }
 }
\ No newline at end of file
@@ -0,0 +1,15 @@
+use crate::{parser::cst::{CstNode, CstNodeKind}, span::Span};
+
+
+#[derive(Debug)]
+pub struct StartDirective {
+    pub span: Span
+}
+
+impl StartDirective {
+    pub fn from(node: &CstNode) -> Self {
+        assert_eq!(node.kind, CstNodeKind::StartDirective);
+
+        Self { span: node.span.unwrap() }
+    }
+}
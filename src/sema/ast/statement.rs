@@ -1,11 +1,14 @@
-use crate::{parser::cst::{CstNode, CstNodeKind}, sema::ast::{export_directive::ExportDirective, import_directive::ImportDirective, instruction::Instruction, label_directive::LabelDirective, r#macro::Macro, res_directive::ResDirective}};
+use crate::{parser::cst::{CstNode, CstNodeKind}, sema::ast::{equ_directive::EquDirective, expression::Expression, export_directive::ExportDirective, instruction_arg::InstructionArg, macro_arg::MacroArg, import_directive::ImportDirective, instruction::Instruction, label_directive::LabelDirective, r#macro::Macro, res_directive::ResDirective, start_directive::StartDirective}};
 
 
+#[derive(Debug)]
 pub enum Statement {
     ImportDirective(ImportDirective),
     ExportDirective(ExportDirective),
-    ResDirective(ResDirective),
+    ResDirective(Box<ResDirective>),
+    EquDirective(EquDirective),
     LabelDirective(LabelDirective),
+    StartDirective(StartDirective),
     Instruction(Instruction),
     Macro(Macro)
 }
@@ -17,12 +20,46 @@ impl Statement {
         match node.child(0).kind {
             CstNodeKind::ImportDirective => Statement::ImportDirective(ImportDirective::from(node.child(0))),
             CstNodeKind::ExportDirective => Statement::ExportDirective(ExportDirective::from(node.child(0))),
-            CstNodeKind::ResDirective => Statement::ResDirective(ResDirective::from(node.child(0))),
+            CstNodeKind::ResDirective => Statement::ResDirective(Box::new(ResDirective::from(node.child(0)))),
+            CstNodeKind::EquDirective => Statement::EquDirective(EquDirective::from(node.child(0))),
             CstNodeKind::LabelDirective => Statement::LabelDirective(LabelDirective::from(node.child(0))),
+            CstNodeKind::StartDirective => Statement::StartDirective(StartDirective::from(node.child(0))),
             CstNodeKind::Instruction => Statement::Instruction(Instruction::from(node.child(0))),
             CstNodeKind::Macro => Statement::Macro(Macro::from(node.child(0))),
             _ => unreachable!()
         }
         
     }
+
+    pub fn expressions(&self) -> Vec<&Expression> {
+        match self {
+            Statement::Instruction(node) => node.args.iter().filter_map(|arg| if let InstructionArg::Number(expression) = arg { Some(expression) } else { None }).collect(),
+            Statement::Macro(node) => node.args.iter().filter_map(|arg| if let MacroArg::Number(expression) = arg { Some(expression) } else { None }).collect(),
+            Statement::ResDirective(node) => {
+                let mut expressions = node.data_type.expressions();
+                if let Some(assignment) = &node.assignment {
+                    expressions.extend(assignment.expressions());
+                }
+                expressions
+            },
+            Statement::EquDirective(node) => vec![&node.value],
+            _ => Vec::new()
+        }
+    }
+
+    pub fn expressions_mut(&mut self) -> Vec<&mut Expression> {
+        match self {
+            Statement::Instruction(node) => node.args.iter_mut().filter_map(|arg| if let InstructionArg::Number(expression) = arg { Some(expression) } else { None }).collect(),
+            Statement::Macro(node) => node.args.iter_mut().filter_map(|arg| if let MacroArg::Number(expression) = arg { Some(expression) } else { None }).collect(),
+            Statement::ResDirective(node) => {
+                let mut expressions = node.data_type.expressions_mut();
+                if let Some(assignment) = &mut node.assignment {
+                    expressions.extend(assignment.expressions_mut());
+                }
+                expressions
+            },
+            Statement::EquDirective(node) => vec![&mut node.value],
+            _ => Vec::new()
+        }
+    }
 }
\ No newline at end of file
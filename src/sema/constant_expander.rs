@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use crate::parser::MAX_EXPRESSION_DEPTH;
+use crate::sema::ast::expression::{Expression, ExpressionKind};
+use crate::sema::ast::file::File;
+use crate::sema::ast::macro_arg::MacroArg;
+use crate::sema::ast::statement::Statement;
+use crate::sema::sema_error::{SemaError, SemaErrorKind};
+use crate::span::Span;
+
+
+
+const MAX_EXPANDED_DEPTH: usize = 8 * MAX_EXPRESSION_DEPTH;
+const MAX_EXPANDED_SIZE: usize = 65536;
+
+
+struct ConstantExpander {
+    definitions: HashMap<String, (Expression, Span)>,
+    resolved: HashMap<String, Option<(Expression, usize)>>,
+    stack: Vec<String>,
+    expanded: usize,
+    too_large: bool,
+    errors: Vec<SemaError>
+}
+
+
+pub fn expand_constants(file: &mut File) -> Result<(), Vec<SemaError>> {
+    let mut expander = ConstantExpander { definitions: HashMap::new(), resolved: HashMap::new(), stack: Vec::new(), expanded: 0, too_large: false, errors: Vec::new() };
+    let mut names = Vec::new();
+
+    for stmt in &file.statements {
+        if let Statement::EquDirective(node) = stmt {
+            let name = node.label.str.clone().unwrap();
+            expander.definitions.insert(name.clone(), (node.value.clone(), node.label.span));
+            names.push(name);
+        }
+    }
+
+    for name in &names {
+        expander.resolve(name);
+    }
+
+    for stmt in &mut file.statements {
+        if let Statement::EquDirective(node) = stmt {
+            if let Some(Some((value, _))) = expander.resolved.get(node.label.str.as_ref().unwrap()) {
+                node.value.kind = value.kind.clone();
+            }
+            continue;
+        }
+
+        if let Statement::Macro(node) = stmt {
+            for arg in &mut node.args {
+                if let MacroArg::Label(label) = arg && expander.definitions.contains_key(label.str.as_ref().unwrap()) {
+                    let mut expression = Expression { kind: ExpressionKind::Label(label.clone()), span: label.span };
+                    expander.expand(&mut expression);
+                    *arg = MacroArg::Number(expression);
+                }
+            }
+        }
+
+        for expression in stmt.expressions_mut() {
+            expander.expand(expression);
+        }
+    }
+
+    if expander.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(expander.errors)
+    }
+}
+
+
+impl ConstantExpander {
+    fn expand(&mut self, expression: &mut Expression) {
+        self.expanded = expression.size();
+        self.too_large = false;
+
+        self.substitute(expression);
+
+        if self.too_large {
+            self.errors.push(SemaError::new(SemaErrorKind::ConstantTooLarge, format!("Expression expands to more than {} terms.", MAX_EXPANDED_SIZE).as_str(), expression.span)
+                .with_note("Every use of a constant is replaced by its whole expression."));
+        }
+    }
+
+    fn resolve(&mut self, name: &str) -> Option<(Expression, usize)> {
+        if let Some(value) = self.resolved.get(name) {
+            return value.clone();
+        }
+
+        if let Some(position) = self.stack.iter().position(|other| other == name) {
+            let mut chain = self.stack[position..].to_vec();
+            chain.push(name.to_string());
+
+            self.errors.push(SemaError::new(SemaErrorKind::ConstantCycle, format!("Constant {} is defined in terms of itself.", name).as_str(), self.definitions[name].1)
+                .with_note(format!("The definitions form a cycle: {}.", chain.join(" -> ")).as_str()));
+
+            for member in &self.stack[position..] {
+                self.resolved.insert(member.clone(), None);
+            }
+            return None;
+        }
+
+        let mut value = self.definitions[name].0.clone();
+        let outer = (self.expanded, self.too_large);
+        self.expanded = value.size();
+        self.too_large = false;
+
+        self.stack.push(name.to_string());
+        let complete = self.substitute(&mut value);
+        self.stack.pop();
+
+        let (size, too_large) = (self.expanded, self.too_large);
+        (self.expanded, self.too_large) = outer;
+
+        if self.resolved.contains_key(name) {
+            return None;
+        }
+
+        let error = if too_large {
+            Some(format!("Constant {} expands to an expression of more than {} terms.", name, MAX_EXPANDED_SIZE))
+        } else if complete && value.depth() > MAX_EXPANDED_DEPTH {
+            Some(format!("Constant {} expands to an expression more than {} levels deep.", name, MAX_EXPANDED_DEPTH))
+        } else {
+            None
+        };
+
+        if let Some(desc) = &error {
+            self.errors.push(SemaError::new(SemaErrorKind::ConstantTooLarge, desc, self.definitions[name].1)
+                .with_note("Every use of a constant is replaced by its whole expression."));
+        }
+
+        if error.is_some() || !complete {
+            self.resolved.insert(name.to_string(), None);
+            return None;
+        }
+
+        self.resolved.insert(name.to_string(), Some((value.clone(), size)));
+        Some((value, size))
+    }
+
+    fn substitute(&mut self, expression: &mut Expression) -> bool {
+        match &mut expression.kind {
+            ExpressionKind::Number(_) => true,
+            ExpressionKind::Label(label) => {
+                let name = label.str.clone().unwrap();
+
+                if !self.definitions.contains_key(&name) {
+                    return true;
+                }
+
+                match self.resolve(&name) {
+                    Some((value, size)) if self.expanded + size - 1 <= MAX_EXPANDED_SIZE => {
+                        self.expanded += size - 1;
+                        expression.kind = value.kind;
+                        true
+                    },
+                    Some(_) => {
+                        self.too_large = true;
+                        false
+                    },
+                    None => false
+                }
+            },
+            ExpressionKind::Unary(_, operand) => self.substitute(operand),
+            ExpressionKind::Binary(_, lhs, rhs) => {
+                let complete = self.substitute(lhs);
+                self.substitute(rhs) && complete
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::sema::ast::file::File;
+use crate::sema::ast::statement::Statement;
+use crate::sema::sema_error::{SemaError, SemaErrorKind};
+
+
+
+pub fn check_entry_points(file: &File) -> Result<(), Vec<SemaError>> {
+    let mut errors = Vec::new();
+    let mut first_line = None;
+
+    for stmt in &file.statements {
+        if let Statement::StartDirective(node) = stmt {
+            match first_line {
+                None => first_line = Some(node.span.line),
+                Some(line) => errors.push(SemaError::new(SemaErrorKind::EntryPointRedefined, format!("Entry point redefined, previously defined on line {}.", line).as_str(), node.span)
+                    .with_help("A program has at most one .start directive."))
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
@@ -0,0 +1,229 @@
+use crate::sema::ast::assignment::{Assignment, AssignmentValue};
+use crate::sema::ast::data_type::DataType;
+use crate::sema::ast::expression::{BinaryOperator, Expression, ExpressionKind, UnaryOperator};
+use crate::sema::ast::file::File;
+use crate::sema::ast::instruction_arg::InstructionArg;
+use crate::sema::ast::labels::LabelAccess;
+use crate::sema::ast::macro_arg::MacroArg;
+use crate::sema::ast::statement::Statement;
+use crate::sema::sema_error::{SemaError, SemaErrorKind};
+
+
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone)]
+pub enum Base {
+    Section,
+    Import(String)
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum AddressPart {
+    Whole,
+    Low,
+    High
+}
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone)]
+pub struct Value {
+    pub constant: i32,
+    pub base: Option<Base>,
+    pub part: AddressPart
+}
+
+impl Value {
+    pub fn constant(constant: i32) -> Self {
+        Value { constant, base: None, part: AddressPart::Whole }
+    }
+
+    pub fn address(constant: i32, base: Base) -> Self {
+        Value { constant, base: Some(base), part: AddressPart::Whole }
+    }
+
+    pub fn byte(&self) -> u8 {
+        match self.part {
+            AddressPart::Whole | AddressPart::Low => self.constant as u8,
+            AddressPart::High => (self.constant >> 8) as u8
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.base.is_none() && self.part == AddressPart::Whole
+    }
+}
+
+
+pub fn evaluate_expressions(file: &mut File) -> Result<(), Vec<SemaError>> {
+    let mut errors = Vec::new();
+
+    for stmt in &mut file.statements {
+        match stmt {
+            Statement::Instruction(node) => {
+                for arg in &mut node.args {
+                    if let InstructionArg::Number(expression) = arg {
+                        fold(expression, &mut errors);
+                    }
+                }
+            },
+            Statement::Macro(node) => {
+                for arg in &mut node.args {
+                    if let MacroArg::Number(expression) = arg {
+                        fold(expression, &mut errors);
+                    }
+                }
+            },
+            Statement::EquDirective(node) => fold(&mut node.value, &mut errors),
+            Statement::ResDirective(node) => {
+                fold_data_type(&mut node.data_type, &mut errors);
+
+                if let Some(assignment) = &mut node.assignment {
+                    fold_assignment(assignment, &mut errors);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+
+pub fn evaluate(expression: &Expression, resolve: &dyn Fn(&LabelAccess) -> Option<Value>) -> Result<Value, SemaError> {
+    match &expression.kind {
+        ExpressionKind::Number(n) => Ok(Value::constant(*n)),
+        ExpressionKind::Label(label) => resolve(label).ok_or_else(|| SemaError::new(SemaErrorKind::UndefinedLabel, format!("Undefined label {}.", label.str.as_ref().unwrap()).as_str(), label.span)),
+        ExpressionKind::Unary(operator, operand) => {
+            let value = evaluate(operand, resolve)?;
+
+            if value.part != AddressPart::Whole {
+                return Err(part_error(expression));
+            }
+
+            match operator {
+                UnaryOperator::Low if value.base.is_some() => Ok(Value { part: AddressPart::Low, ..value }),
+                UnaryOperator::High if value.base.is_some() => Ok(Value { part: AddressPart::High, ..value }),
+                _ if value.base.is_some() => Err(SemaError::new(SemaErrorKind::LabelArithmetic, format!("{} cannot be applied to a label address.", operator.symbol()).as_str(), expression.span)),
+                UnaryOperator::Negate => value.constant.checked_neg().map(Value::constant).ok_or_else(|| overflow(format!("-#{}", value.constant), expression)),
+                UnaryOperator::Not => Ok(Value::constant(!value.constant)),
+                UnaryOperator::Low => Ok(Value::constant(value.constant & 0xFF)),
+                UnaryOperator::High => Ok(Value::constant((value.constant >> 8) & 0xFF))
+            }
+        },
+        ExpressionKind::Binary(operator, lhs, rhs) => {
+            let lhs = evaluate(lhs, resolve)?;
+            let rhs_value = evaluate(rhs, resolve)?;
+
+            if lhs.part != AddressPart::Whole || rhs_value.part != AddressPart::Whole {
+                return Err(part_error(expression));
+            }
+
+            let desc = format!("#{} {} #{}", lhs.constant, operator.symbol(), rhs_value.constant);
+
+            let base = match operator {
+                BinaryOperator::Add if lhs.base.is_some() && rhs_value.base.is_some() => {
+                    return Err(SemaError::new(SemaErrorKind::LabelArithmetic, "Two label addresses cannot be added.", expression.span).with_help("Subtract them to get the distance between the labels."));
+                },
+                BinaryOperator::Add => lhs.base.clone().or(rhs_value.base.clone()),
+                BinaryOperator::Subtract if rhs_value.base.is_none() => lhs.base.clone(),
+                BinaryOperator::Subtract if lhs.base == rhs_value.base => None,
+                BinaryOperator::Subtract => {
+                    return Err(SemaError::new(SemaErrorKind::LabelArithmetic, "The distance between these labels is not known until link time.", expression.span).with_note("Only labels defined in this file, or the same imported label, can be subtracted."));
+                },
+                _ if !lhs.is_constant() || !rhs_value.is_constant() => {
+                    return Err(SemaError::new(SemaErrorKind::LabelArithmetic, format!("{} cannot be applied to a label address.", operator.symbol()).as_str(), expression.span).with_help("Label addresses can only be offset with + and -, or split with lo() and hi()."));
+                },
+                _ => None
+            };
+
+            let (lhs, rhs_value) = (lhs.constant, rhs_value.constant);
+
+            let constant = match operator {
+                BinaryOperator::Add => lhs.checked_add(rhs_value).ok_or_else(|| overflow(desc, expression))?,
+                BinaryOperator::Subtract => lhs.checked_sub(rhs_value).ok_or_else(|| overflow(desc, expression))?,
+                BinaryOperator::Multiply => lhs.checked_mul(rhs_value).ok_or_else(|| overflow(desc, expression))?,
+                BinaryOperator::Divide | BinaryOperator::Remainder if rhs_value == 0 => {
+                    return Err(SemaError::new(SemaErrorKind::ArithmeticError, format!("Division by zero in {}.", desc).as_str(), rhs.span));
+                },
+                BinaryOperator::Divide => lhs.checked_div(rhs_value).ok_or_else(|| overflow(desc, expression))?,
+                BinaryOperator::Remainder => lhs.checked_rem(rhs_value).ok_or_else(|| overflow(desc, expression))?,
+                BinaryOperator::ShiftLeft | BinaryOperator::ShiftRight if !(0..32).contains(&rhs_value) => {
+                    return Err(SemaError::new(SemaErrorKind::ArithmeticError, format!("Shift amount #{} is out of range (expected #0 to #31).", rhs_value).as_str(), rhs.span));
+                },
+                BinaryOperator::ShiftLeft => i32::try_from((lhs as i64) << rhs_value).map_err(|_| overflow(desc, expression))?,
+                BinaryOperator::ShiftRight => lhs >> rhs_value,
+                BinaryOperator::And => lhs & rhs_value,
+                BinaryOperator::Or => lhs | rhs_value,
+                BinaryOperator::Xor => lhs ^ rhs_value
+            };
+
+            Ok(Value { constant, base, part: AddressPart::Whole })
+        }
+    }
+}
+
+
+fn fold(expression: &mut Expression, errors: &mut Vec<SemaError>) {
+    if !expression.labels().is_empty() {
+        return;
+    }
+
+    match evaluate(expression, &|_| None) {
+        Ok(value) => expression.kind = ExpressionKind::Number(value.constant),
+        Err(error) => errors.push(error)
+    }
+}
+
+
+fn fold_count(expression: &mut Expression, errors: &mut Vec<SemaError>) {
+    if expression.labels().is_empty() {
+        fold(expression, errors);
+    } else {
+        errors.push(SemaError::new(SemaErrorKind::LabelArithmetic, "Sizes and repetition counts cannot refer to labels.", expression.span).with_note("Sizes are fixed before label addresses are known."));
+    }
+}
+
+
+fn fold_data_type(data_type: &mut DataType, errors: &mut Vec<SemaError>) {
+    match data_type {
+        DataType::Byte => {},
+        DataType::Bytes(n) => fold_count(n, errors),
+        DataType::Arr(n, element) => {
+            fold_count(n, errors);
+            fold_data_type(element, errors);
+        }
+    }
+}
+
+
+fn fold_assignment(assignment: &mut Assignment, errors: &mut Vec<SemaError>) {
+    fold_count(&mut assignment.repetition, errors);
+
+    for value in &mut assignment.values {
+        match value {
+            AssignmentValue::Number(expression) => fold(expression, errors),
+            AssignmentValue::Assignment(inner) => fold_assignment(inner, errors),
+            AssignmentValue::String(_) => {}
+        }
+    }
+}
+
+
+fn part_error(expression: &Expression) -> SemaError {
+    SemaError::new(SemaErrorKind::LabelArithmetic, "The result of lo() or hi() of a label cannot be used in further arithmetic.", expression.span)
+        .with_help("Apply lo() or hi() last, e.g. lo($label + #1).")
+}
+
+
+fn overflow(desc: String, expression: &Expression) -> SemaError {
+    SemaError::new(SemaErrorKind::ArithmeticError, format!("{} overflows a 32-bit value.", desc).as_str(), expression.span)
+        .with_note("Expressions are evaluated with 32-bit signed arithmetic.")
+}
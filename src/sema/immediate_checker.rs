@@ -0,0 +1,115 @@
+use crate::codegen::opcodes::{matching_form, ImmediateField, Operand};
+use crate::sema::ast::assignment::{Assignment, AssignmentValue};
+use crate::sema::ast::file::File;
+use crate::sema::ast::instruction::Instruction;
+use crate::sema::ast::statement::Statement;
+use crate::sema::sema_error::{SemaError, SemaErrorKind};
+use crate::sema::sema_warning::{SemaWarning, WarningKind};
+
+
+
+pub const DATA_FIELD: ImmediateField = ImmediateField { bits: 8, signed: false };
+
+
+pub fn check_immediates(file: &File, warnings: &mut Vec<SemaWarning>) -> Result<(), Vec<SemaError>> {
+    let mut errors = Vec::new();
+
+    for stmt in &file.statements {
+        match stmt {
+            Statement::Instruction(node) => check_instruction(node, &mut errors, warnings),
+            Statement::ResDirective(node) => {
+                if let Some(assignment) = &node.assignment {
+                    check_assignment(assignment, &mut errors, warnings);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+
+fn check_instruction(instruction: &Instruction, errors: &mut Vec<SemaError>, warnings: &mut Vec<SemaWarning>) {
+    let Some((_, form)) = matching_form(instruction) else {
+        return;
+    };
+
+    for ((arg, operand), span) in instruction.args.iter().zip(form).zip(&instruction.arg_spans) {
+        let Operand::Immediate(field) = operand else {
+            continue;
+        };
+
+        let Some(value) = arg.immediate_value() else {
+            continue;
+        };
+
+        let value = value as i64;
+        let (min, max) = field_range(field);
+        let signedness = if field.signed { "signed" } else { "unsigned" };
+
+        if value >= min && value <= max {
+            continue;
+        }
+
+        if let Some(reinterpreted) = reinterpret(value, field) {
+            warnings.push(SemaWarning::new(WarningKind::TruncatedImmediate, format!("Immediate #{} does not fit the {}-bit {} field of {} and is encoded as #{}.", value, field.bits, signedness, instruction.mnemonic, reinterpreted).as_str(), *span).with_note(format!("Valid values are #{} to #{}.", min, max).as_str()));
+        } else {
+            errors.push(SemaError::new(SemaErrorKind::ImmediateOutOfRange, format!("Immediate #{} does not fit the {}-bit {} field of {} (expected #{} to #{}).", value, field.bits, signedness, instruction.mnemonic, min, max).as_str(), *span));
+        }
+    }
+}
+
+
+fn check_assignment(assignment: &Assignment, errors: &mut Vec<SemaError>, warnings: &mut Vec<SemaWarning>) {
+    for value in &assignment.values {
+        match value {
+            AssignmentValue::Number(expression) => {
+                let Some(value) = expression.value() else {
+                    continue;
+                };
+
+                let value = value as i64;
+                let (min, max) = field_range(&DATA_FIELD);
+
+                if value >= min && value <= max {
+                    continue;
+                }
+
+                if let Some(reinterpreted) = reinterpret(value, &DATA_FIELD) {
+                    warnings.push(SemaWarning::new(WarningKind::TruncatedImmediate, format!("Initializer value #{} does not fit in a byte and is encoded as #{}.", value, reinterpreted).as_str(), expression.span).with_note(format!("Valid values are #{} to #{}.", min, max).as_str()));
+                } else {
+                    errors.push(SemaError::new(SemaErrorKind::ImmediateOutOfRange, format!("Initializer value #{} does not fit in a byte (expected #{} to #{}).", value, min, max).as_str(), expression.span));
+                }
+            },
+            AssignmentValue::Assignment(inner) => check_assignment(inner, errors, warnings),
+            AssignmentValue::String(_) => {}
+        }
+    }
+}
+
+
+pub fn field_range(field: &ImmediateField) -> (i64, i64) {
+    if field.signed {
+        (-(1 << (field.bits - 1)), (1 << (field.bits - 1)) - 1)
+    } else {
+        (0, (1 << field.bits) - 1)
+    }
+}
+
+
+pub fn reinterpret(value: i64, field: &ImmediateField) -> Option<i64> {
+    let modulus = 1 << field.bits;
+
+    if field.signed && value >= modulus / 2 && value < modulus {
+        Some(value - modulus)
+    } else if !field.signed && value < 0 && value >= -(modulus / 2) {
+        Some(value + modulus)
+    } else {
+        None
+    }
+}
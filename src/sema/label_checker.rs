@@ -0,0 +1,71 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::sema::ast::file::File;
+use crate::sema::ast::instruction_arg::InstructionArg;
+use crate::sema::ast::statement::Statement;
+use crate::sema::sema_warning::{SemaWarning, WarningKind};
+use crate::span::Span;
+
+
+
+pub fn check_labels(file: &File, warnings: &mut Vec<SemaWarning>) {
+    let mut definitions: Vec<(&str, Span)> = Vec::new();
+    let mut used: HashSet<&str> = HashSet::new();
+
+    for stmt in &file.statements {
+        match stmt {
+            Statement::LabelDirective(node) => definitions.push((node.label.str.as_deref().unwrap(), node.label.span)),
+            Statement::ResDirective(node) => definitions.push((node.label.str.as_deref().unwrap(), node.label.span)),
+            Statement::ImportDirective(node) => definitions.push((node.label_intern.str.as_deref().unwrap(), node.label_intern.span)),
+            Statement::ExportDirective(node) => { used.insert(node.label_intern.str.as_deref().unwrap()); },
+            Statement::Instruction(node) => {
+                for arg in &node.args {
+                    if let InstructionArg::Label(label) = arg {
+                        used.insert(label.str.as_deref().unwrap());
+                    }
+                }
+            },
+            Statement::Macro(_) | Statement::StartDirective(_) | Statement::EquDirective(_) => {}
+        }
+
+        for expression in stmt.expressions() {
+            used.extend(expression.labels().into_iter().map(|label| label.str.as_deref().unwrap()));
+        }
+    }
+
+    let defined: HashMap<&str, Span> = definitions.iter().copied().collect();
+
+    for (name, span) in &definitions {
+        let scope_prefix = format!("{name}>");
+        let is_scope = defined.keys().chain(used.iter()).any(|other| other.starts_with(&scope_prefix));
+
+        if !used.contains(name) && !is_scope {
+            warnings.push(SemaWarning::new(WarningKind::UnusedLabel, format!("Label {} is never used.", name).as_str(), *span));
+        }
+
+        if let Some((shadowed, shadowed_span)) = shadowed_label(name, &defined) {
+            warnings.push(SemaWarning::new(WarningKind::ShadowedLabel, format!("Label {} shadows label {} defined on line {}.", name, shadowed, shadowed_span.line).as_str(), *span)
+                .with_help(format!("Refer to the outer label as ${} and to this one by its full path.", shadowed).as_str()));
+        }
+    }
+}
+
+
+fn shadowed_label(name: &str, defined: &HashMap<&str, Span>) -> Option<(String, Span)> {
+    let parts: Vec<&str> = name.split('>').collect();
+    let (local, scopes) = parts.split_last().unwrap();
+
+    for depth in (0..scopes.len()).rev() {
+        let mut candidate = scopes[..depth].join(">");
+        if !candidate.is_empty() {
+            candidate.push('>');
+        }
+        candidate.push_str(local);
+
+        if let Some(span) = defined.get(candidate.as_str()) {
+            return Some((candidate, *span));
+        }
+    }
+
+    None
+}
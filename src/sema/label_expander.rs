@@ -1,13 +1,14 @@
+use crate::sema::ast::equ_directive::EquDirective;
 use crate::sema::ast::export_directive::ExportDirective;
 use crate::sema::ast::file::File;
 use crate::sema::ast::import_directive::ImportDirective;
 use crate::sema::ast::label_directive::LabelDirective;
+use crate::sema::ast::labels::LabelAccess;
 use crate::sema::ast::r#macro::Macro;
 use crate::sema::ast::macro_arg::MacroArg;
 use crate::sema::ast::res_directive::ResDirective;
 use crate::sema::ast::statement::Statement;
-use crate::sema::sema_error::SemaError;
-use crate::parser::cst::{CstNode, CstNodeKind};
+use crate::sema::sema_error::{SemaError, SemaErrorKind};
 
 
 
@@ -21,31 +22,39 @@ pub fn expand_labels(file: &mut File) -> Result<(), SemaError> {
             Statement::ExportDirective(node) => expand_export_directive(node, &mut stack)?,
             Statement::ImportDirective(node) => expand_import_directive(node, &mut stack)?,
             Statement::ResDirective(node) => expand_res_directive(node, &mut stack)?,
+            Statement::EquDirective(node) => expand_equ_directive(node, &mut stack)?,
             Statement::LabelDirective(node) => expand_label_directive(node, &mut stack)?,
-            Statement::Instruction(node) => {},
+            Statement::Instruction(_) => {},
+            Statement::StartDirective(_) => {},
             Statement::Macro(node) => expand_macro(node, &mut stack)?,
         }
+
+        for expression in stmt.expressions_mut() {
+            for label in expression.labels_mut() {
+                expand_label_access(label, &stack)?;
+            }
+        }
     }
 
     Ok(())
 }
 
-fn expand_export_directive(node: &mut ExportDirective, stack: &mut Vec<String>) -> Result<(), SemaError>{
+fn expand_export_directive(node: &mut ExportDirective, stack: &mut [String]) -> Result<(), SemaError>{
     let label_intern = &node.label_intern;
 
     let mut str = String::new();
 
     if label_intern.prefix_count as usize > stack.len() {
-        return Err(SemaError::new("Auto nesting too deep.", node.line))
+        return Err(SemaError::new(SemaErrorKind::AutoNestingTooDeep, "Auto nesting too deep.", label_intern.span).with_note("Each > prefix refers to one enclosing label scope."))
     }
 
-    for i in 0..label_intern.prefix_count as usize {
-        str.push_str(&stack[i]);
+    for scope in &stack[..label_intern.prefix_count as usize] {
+        str.push_str(scope);
         str.push('>');
     }
 
     for scope in &label_intern.scopes {
-        str.push_str(&scope);
+        str.push_str(scope);
         str.push('>');
     }
 
@@ -60,7 +69,7 @@ fn expand_export_directive(node: &mut ExportDirective, stack: &mut Vec<String>)
     let label_extern = &node.label_extern;
 
     for scope in &label_extern.scopes {
-        str.push_str(&scope);
+        str.push_str(scope);
         str.push('>');
     }
 
@@ -75,17 +84,17 @@ fn expand_export_directive(node: &mut ExportDirective, stack: &mut Vec<String>)
 }
 
 
-fn expand_import_directive(node: &mut ImportDirective, stack: &mut Vec<String>) -> Result<(), SemaError>{
+fn expand_import_directive(node: &mut ImportDirective, stack: &mut [String]) -> Result<(), SemaError>{
     let label_intern = &node.label_intern;
 
     let mut str = String::new();
 
     if label_intern.prefix_count as usize > stack.len() {
-        return Err(SemaError::new("Auto nesting too deep.", node.line))
+        return Err(SemaError::new(SemaErrorKind::AutoNestingTooDeep, "Auto nesting too deep.", label_intern.span).with_note("Each > prefix refers to one enclosing label scope."))
     }
 
-    for i in 0..label_intern.prefix_count as usize {
-        str.push_str(&stack[i]);
+    for scope in &stack[..label_intern.prefix_count as usize] {
+        str.push_str(scope);
         str.push('>');
     }
 
@@ -99,7 +108,7 @@ fn expand_import_directive(node: &mut ImportDirective, stack: &mut Vec<String>)
     let label_extern = &node.label_extern;
 
     for scope in &label_extern.scopes {
-        str.push_str(&scope);
+        str.push_str(scope);
         str.push('>');
     }
 
@@ -111,18 +120,18 @@ fn expand_import_directive(node: &mut ImportDirective, stack: &mut Vec<String>)
 }
 
 
-fn expand_res_directive(node: &mut ResDirective, stack: &mut Vec<String>) -> Result<(), SemaError> {
+fn expand_res_directive(node: &mut ResDirective, stack: &mut [String]) -> Result<(), SemaError> {
 
     let label = &node.label;
 
     let mut str = String::new();
 
     if label.prefix_count as usize > stack.len() {
-        return Err(SemaError::new("Auto nesting too deep.", node.line))
+        return Err(SemaError::new(SemaErrorKind::AutoNestingTooDeep, "Auto nesting too deep.", label.span).with_note("Each > prefix refers to one enclosing label scope."))
     }
 
-    for i in 0..label.prefix_count as usize {
-        str.push_str(&stack[i]);
+    for scope in &stack[..label.prefix_count as usize] {
+        str.push_str(scope);
         str.push('>');
     }
 
@@ -137,6 +146,29 @@ fn expand_res_directive(node: &mut ResDirective, stack: &mut Vec<String>) -> Res
 }
 
 
+fn expand_equ_directive(node: &mut EquDirective, stack: &mut [String]) -> Result<(), SemaError> {
+
+    let label = &node.label;
+
+    let mut str = String::new();
+
+    if label.prefix_count as usize > stack.len() {
+        return Err(SemaError::new(SemaErrorKind::AutoNestingTooDeep, "Auto nesting too deep.", label.span).with_note("Each > prefix refers to one enclosing label scope."))
+    }
+
+    for scope in &stack[..label.prefix_count as usize] {
+        str.push_str(scope);
+        str.push('>');
+    }
+
+    str.push_str(&label.label);
+
+    node.label.str = Some(str);
+
+    Ok(())
+}
+
+
 fn expand_label_directive(node: &mut LabelDirective, stack: &mut Vec<String>) -> Result<(), SemaError> {
     
     let label = &node.label;
@@ -144,11 +176,11 @@ fn expand_label_directive(node: &mut LabelDirective, stack: &mut Vec<String>) ->
     let mut str = String::new();
 
     if label.prefix_count as usize > stack.len() {
-        return Err(SemaError::new("Auto nesting too deep.", node.line))
+        return Err(SemaError::new(SemaErrorKind::AutoNestingTooDeep, "Auto nesting too deep.", label.span).with_note("Each > prefix refers to one enclosing label scope."))
     }
 
-    for i in 0..label.prefix_count as usize {
-        str.push_str(&stack[i]);
+    for scope in &stack[..label.prefix_count as usize] {
+        str.push_str(scope);
         str.push('>');
     }
 
@@ -172,32 +204,38 @@ fn expand_label_directive(node: &mut LabelDirective, stack: &mut Vec<String>) ->
 }
 
 
-fn expand_macro(node: &mut Macro, stack: &mut Vec<String>) -> Result<(), SemaError> {
+fn expand_macro(node: &mut Macro, stack: &mut [String]) -> Result<(), SemaError> {
     for arg in &mut node.args {
         if let MacroArg::Label(label) = arg {
+            expand_label_access(label, stack)?;
+        }
+    }
 
-            let mut str = String::new();
+    Ok(())
+}
 
-            if label.prefix_count as usize > stack.len() {
-                return Err(SemaError::new("Auto nesting too deep.", node.line))
-            }
 
-            for i in 0..label.prefix_count as usize {
-                str.push_str(&stack[i]);
-                str.push('>');
-            }
+fn expand_label_access(label: &mut LabelAccess, stack: &[String]) -> Result<(), SemaError> {
+    let mut str = String::new();
 
-            for scope in &label.scopes {
-                str.push_str(&scope);
-                str.push('>');
-            }
+    if label.prefix_count as usize > stack.len() {
+        return Err(SemaError::new(SemaErrorKind::AutoNestingTooDeep, "Auto nesting too deep.", label.span).with_note("Each > prefix refers to one enclosing label scope."))
+    }
 
-            str.push_str(&label.label);
+    for scope in &stack[..label.prefix_count as usize] {
+        str.push_str(scope);
+        str.push('>');
+    }
 
-            label.str = Some(str);
-        }
+    for scope in &label.scopes {
+        str.push_str(scope);
+        str.push('>');
     }
 
+    str.push_str(&label.label);
+
+    label.str = Some(str);
+
     Ok(())
 
 }
\ No newline at end of file
@@ -0,0 +1,103 @@
+use crate::sema::ast::file::File;
+use crate::sema::ast::instruction::Instruction;
+use crate::sema::ast::instruction_arg::InstructionArg;
+use crate::sema::ast::r#macro::Macro;
+use crate::sema::ast::macro_arg::MacroArg;
+use crate::sema::ast::statement::Statement;
+use crate::sema::sema_error::{SemaError, SemaErrorKind};
+
+
+
+pub static MACRO_SCRATCH_REGISTER: &str = "lr15";
+
+
+pub fn expand_macros(file: &mut File) -> Result<(), Vec<SemaError>> {
+    let mut errors = Vec::new();
+    let mut statements = Vec::new();
+
+    for stmt in file.statements.drain(..) {
+        match stmt {
+            Statement::Macro(node) => match lower_macro(node) {
+                Ok(instructions) => statements.extend(instructions.into_iter().map(Statement::Instruction)),
+                Err(error) => errors.push(error)
+            },
+            _ => statements.push(stmt)
+        }
+    }
+
+    file.statements = statements;
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+
+fn lower_macro(node: Macro) -> Result<Vec<Instruction>, SemaError> {
+    match node.mnemonic.as_str() {
+        "!mov" => lower_mov(node),
+        "!b" => lower_branch(node, "ba"),
+        "!bl" => lower_branch(node, "bal"),
+        _ => unreachable!()
+    }
+}
+
+
+fn lower_mov(node: Macro) -> Result<Vec<Instruction>, SemaError> {
+    let valid = matches!(
+        node.args.as_slice(),
+        [MacroArg::LongRegister(_), MacroArg::Label(_) | MacroArg::Number(_)] | [MacroArg::Register(_), MacroArg::Number(_)]
+    );
+
+    if !valid {
+        return Err(mismatch_error(&node, "<long register> <label> or <long register> <immediate> or <register> <immediate>"));
+    }
+
+    let args = node.args.into_iter().map(to_instruction_arg).collect();
+
+    Ok(vec![Instruction { mnemonic: "mov".to_string(), condition: node.condition, args, arg_spans: node.arg_spans, span: node.span }])
+}
+
+
+fn lower_branch(mut node: Macro, mnemonic: &str) -> Result<Vec<Instruction>, SemaError> {
+    let valid = matches!(node.args.as_slice(), [MacroArg::Label(_) | MacroArg::Number(_) | MacroArg::LongRegister(_)]);
+
+    if !valid {
+        return Err(mismatch_error(&node, "<label> or <immediate> or <long register>"));
+    }
+
+    let target = to_instruction_arg(node.args.remove(0));
+    let target_span = node.arg_spans[0];
+
+    if let InstructionArg::LongRegister(_) = target {
+        return Ok(vec![Instruction { mnemonic: mnemonic.to_string(), condition: node.condition, args: vec![target], arg_spans: vec![target_span], span: node.span }]);
+    }
+
+    let scratch = || InstructionArg::LongRegister(MACRO_SCRATCH_REGISTER.to_string());
+
+    Ok(vec![
+        Instruction { mnemonic: "mov".to_string(), condition: None, args: vec![scratch(), target], arg_spans: vec![node.span, target_span], span: node.span },
+        Instruction { mnemonic: mnemonic.to_string(), condition: node.condition, args: vec![scratch()], arg_spans: vec![node.span], span: node.span }
+    ])
+}
+
+
+fn to_instruction_arg(arg: MacroArg) -> InstructionArg {
+    match arg {
+        MacroArg::Register(name) => InstructionArg::Register(name),
+        MacroArg::Number(n) => InstructionArg::Number(n),
+        MacroArg::LongRegister(name) => InstructionArg::LongRegister(name),
+        MacroArg::Label(label) => InstructionArg::Label(label)
+    }
+}
+
+
+fn mismatch_error(node: &Macro, expected: &str) -> SemaError {
+    let found: Vec<String> = node.args.iter().map(|arg| arg.desc()).collect();
+
+    let found_desc = if found.is_empty() { "no operands".to_string() } else { found.join(" ") };
+
+    SemaError::new(SemaErrorKind::OperandMismatch, format!("{} expects {}, found {}.", node.mnemonic, expected, found_desc).as_str(), node.span)
+}
@@ -0,0 +1,37 @@
+use crate::codegen::opcodes::{matching_form, operand_forms};
+use crate::sema::ast::file::File;
+use crate::sema::ast::instruction::Instruction;
+use crate::sema::ast::statement::Statement;
+use crate::sema::sema_error::{SemaError, SemaErrorKind};
+
+
+
+pub fn check_operands(file: &File) -> Result<(), Vec<SemaError>> {
+    let mut errors = Vec::new();
+
+    for stmt in &file.statements {
+        if let Statement::Instruction(node) = stmt && matching_form(node).is_none() {
+            errors.push(SemaError::new(SemaErrorKind::OperandMismatch, mismatch_desc(node).as_str(), node.span));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+
+fn mismatch_desc(instruction: &Instruction) -> String {
+    let expected: Vec<String> = operand_forms(&instruction.mnemonic)
+        .iter()
+        .map(|form| form.iter().map(|operand| operand.desc()).collect::<Vec<String>>().join(" "))
+        .collect();
+
+    let found: Vec<String> = instruction.args.iter().map(|arg| arg.desc()).collect();
+
+    let found_desc = if found.is_empty() { "no operands".to_string() } else { found.join(" ") };
+
+    format!("{} expects {}, found {}.", instruction.mnemonic, expected.join(" or "), found_desc)
+}
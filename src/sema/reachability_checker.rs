@@ -0,0 +1,35 @@
+use crate::sema::ast::file::File;
+use crate::sema::ast::instruction::Instruction;
+use crate::sema::ast::statement::Statement;
+use crate::sema::sema_warning::{SemaWarning, WarningKind};
+
+
+
+pub fn check_reachability(file: &File, warnings: &mut Vec<SemaWarning>) {
+    let mut branch_line = None;
+    let mut reported = false;
+
+    for stmt in &file.statements {
+        match stmt {
+            Statement::LabelDirective(_) | Statement::StartDirective(_) => branch_line = None,
+            Statement::Instruction(node) => {
+                if let Some(line) = branch_line && !reported {
+                    warnings.push(SemaWarning::new(WarningKind::UnreachableCode, "Unreachable instruction.", node.span)
+                        .with_note(format!("The unconditional branch on line {} never falls through and no label follows it.", line).as_str()));
+                    reported = true;
+                }
+
+                if branch_line.is_none() && is_unconditional_branch(node) {
+                    branch_line = Some(node.span.line);
+                    reported = false;
+                }
+            },
+            _ => {}
+        }
+    }
+}
+
+
+fn is_unconditional_branch(instruction: &Instruction) -> bool {
+    matches!(instruction.mnemonic.as_str(), "ba" | "br") && matches!(instruction.condition.as_deref(), None | Some("al"))
+}
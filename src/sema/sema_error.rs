@@ -1,18 +1,78 @@
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::span::Span;
 
 
+pub enum SemaErrorKind {
+    UndefinedLabel,
+    Redefinition,
+    AutoNestingTooDeep,
+    EntryPointRedefined,
+    ConstantExported,
+    ConstantCycle,
+    ConstantTooLarge,
+    OperandMismatch,
+    ImmediateOutOfRange,
+    SizeOutOfRange,
+    ArithmeticError,
+    LabelArithmetic,
+}
+
 pub struct SemaError {
+    kind: SemaErrorKind,
     desc: String,
-    line: u32,
+    span: Span,
+    notes: Vec<String>,
+    help: Option<String>
 }
 
 
 impl SemaError {
     pub fn desc(&self) -> String {
-        format!("*** SEMA ERROR [LINE {}]: {}", self.line, self.desc)   
+        format!("*** SEMA ERROR [LINE {}, COLUMN {}]: {}", self.span.line, self.span.column, self.desc)   
        
     }
 
-    pub fn new(desc: &str, line: u32) -> Self {
-        SemaError { desc: desc.to_string(), line }
+    pub fn new(kind: SemaErrorKind, desc: &str, span: Span) -> Self {
+        SemaError { kind, desc: desc.to_string(), span, notes: Vec::new(), help: None }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn with_note(mut self, note: &str) -> Self {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    pub fn with_help(mut self, help: &str) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = Diagnostic::error(self.kind.code(), &self.desc, Some(self.span));
+        diagnostic.notes = self.notes.clone();
+        diagnostic.help = self.help.clone();
+        diagnostic
     }
-}
\ No newline at end of file
+}
+
+impl SemaErrorKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UndefinedLabel => "E0301",
+            Self::Redefinition => "E0302",
+            Self::AutoNestingTooDeep => "E0303",
+            Self::EntryPointRedefined => "E0304",
+            Self::ConstantExported => "E0305",
+            Self::ConstantCycle => "E0306",
+            Self::ConstantTooLarge => "E0307",
+            Self::OperandMismatch => "E0308",
+            Self::ImmediateOutOfRange => "E0309",
+            Self::SizeOutOfRange => "E0310",
+            Self::ArithmeticError => "E0311",
+            Self::LabelArithmetic => "E0312"
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::diagnostics::diagnostic::Diagnostic;
+use crate::span::Span;
+
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, Copy)]
+pub enum WarningKind {
+    UnusedLabel,
+    ShadowedLabel,
+    TruncatedImmediate,
+    UnreachableCode,
+    RawEntryPoint
+}
+
+impl WarningKind {
+    pub const ALL: [WarningKind; 5] = [Self::UnusedLabel, Self::ShadowedLabel, Self::TruncatedImmediate, Self::UnreachableCode, Self::RawEntryPoint];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::UnusedLabel => "unused-label",
+            Self::ShadowedLabel => "shadowed-label",
+            Self::TruncatedImmediate => "truncated-immediate",
+            Self::UnreachableCode => "unreachable-code",
+            Self::RawEntryPoint => "raw-entry-point"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnusedLabel => "W0301",
+            Self::ShadowedLabel => "W0302",
+            Self::TruncatedImmediate => "W0303",
+            Self::UnreachableCode => "W0304",
+            Self::RawEntryPoint => "W0001"
+        }
+    }
+
+    pub fn enabled_by_default(&self) -> bool {
+        !matches!(self, Self::UnusedLabel)
+    }
+
+    pub fn defaults() -> Vec<WarningKind> {
+        Self::ALL.into_iter().filter(|kind| kind.enabled_by_default()).collect()
+    }
+}
+
+pub struct SemaWarning {
+    kind: WarningKind,
+    desc: String,
+    span: Span,
+    notes: Vec<String>,
+    help: Option<String>
+}
+
+
+impl SemaWarning {
+    pub fn desc(&self) -> String {
+        format!("*** SEMA WARNING [LINE {}, COLUMN {}]: {} [{}]", self.span.line, self.span.column, self.desc, self.kind.name())
+    }
+
+    pub fn new(kind: WarningKind, desc: &str, span: Span) -> Self {
+        SemaWarning { kind, desc: desc.to_string(), span, notes: Vec::new(), help: None }
+    }
+
+    pub fn kind(&self) -> WarningKind {
+        self.kind
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn with_note(mut self, note: &str) -> Self {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    pub fn with_help(mut self, help: &str) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = Diagnostic::warning(self.kind.code(), format!("{} [{}]", self.desc, self.kind.name()).as_str(), Some(self.span));
+        diagnostic.notes = self.notes.clone();
+        diagnostic.help = self.help.clone();
+        diagnostic
+    }
+}
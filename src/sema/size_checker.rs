@@ -0,0 +1,61 @@
+use crate::codegen::layout::ADDRESS_SPACE_SIZE;
+use crate::sema::ast::assignment::{Assignment, AssignmentValue};
+use crate::sema::ast::data_type::DataType;
+use crate::sema::ast::expression::Expression;
+use crate::sema::ast::file::File;
+use crate::sema::ast::statement::Statement;
+use crate::sema::sema_error::{SemaError, SemaErrorKind};
+
+
+
+pub fn check_sizes(file: &File) -> Result<(), Vec<SemaError>> {
+    let mut errors = Vec::new();
+
+    for stmt in &file.statements {
+        if let Statement::ResDirective(node) = stmt {
+            check_data_type(&node.data_type, &mut errors);
+
+            if let Some(assignment) = &node.assignment {
+                check_assignment(assignment, &mut errors);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+
+fn check_data_type(data_type: &DataType, errors: &mut Vec<SemaError>) {
+    match data_type {
+        DataType::Byte => {},
+        DataType::Bytes(n) => check_count(n, ".bytes size", errors),
+        DataType::Arr(n, element) => {
+            check_count(n, ".arr length", errors);
+            check_data_type(element, errors);
+        }
+    }
+}
+
+
+fn check_assignment(assignment: &Assignment, errors: &mut Vec<SemaError>) {
+    check_count(&assignment.repetition, "Repetition count", errors);
+
+    for value in &assignment.values {
+        if let AssignmentValue::Assignment(inner) = value {
+            check_assignment(inner, errors);
+        }
+    }
+}
+
+
+fn check_count(count: &Expression, what: &str, errors: &mut Vec<SemaError>) {
+    let value = count.value().unwrap();
+
+    if value as u32 > ADDRESS_SPACE_SIZE {
+        errors.push(SemaError::new(SemaErrorKind::SizeOutOfRange, format!("{} must be between #0 and #{}, found #{}.", what, ADDRESS_SPACE_SIZE, value).as_str(), count.span));
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use crate::sema::ast::file::File;
+use crate::sema::ast::instruction_arg::InstructionArg;
+use crate::sema::ast::labels::LabelAccess;
+use crate::sema::ast::macro_arg::MacroArg;
+use crate::sema::ast::statement::Statement;
+use crate::sema::sema_error::{SemaError, SemaErrorKind};
+use crate::span::Span;
+
+
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub enum SymbolKind {
+    Label,
+    Reservation,
+    Import,
+    Constant
+}
+
+#[derive(Debug)]
+pub struct Symbol {
+    pub kind: SymbolKind,
+    pub span: Span
+}
+
+#[derive(Debug)]
+pub struct SymbolTable {
+    pub symbols: HashMap<String, Symbol>
+}
+
+impl SymbolTable {
+    pub fn get(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.get(name)
+    }
+}
+
+
+pub fn build_symbol_table(file: &File) -> Result<SymbolTable, Vec<SemaError>> {
+    let mut table = SymbolTable { symbols: HashMap::new() };
+    let mut errors = Vec::new();
+
+    for stmt in &file.statements {
+        let definition = match stmt {
+            Statement::LabelDirective(node) => Some((&node.label.str, SymbolKind::Label, node.label.span)),
+            Statement::ResDirective(node) => Some((&node.label.str, SymbolKind::Reservation, node.label.span)),
+            Statement::ImportDirective(node) => Some((&node.label_intern.str, SymbolKind::Import, node.label_intern.span)),
+            Statement::EquDirective(node) => Some((&node.label.str, SymbolKind::Constant, node.label.span)),
+            _ => None
+        };
+
+        if let Some((name, kind, span)) = definition {
+            define(&mut table, name.clone().unwrap(), kind, span, &mut errors);
+        }
+    }
+
+    for stmt in &file.statements {
+        match stmt {
+            Statement::ExportDirective(node) => {
+                check_access(&table, &node.label_intern, &mut errors);
+
+                let name = node.label_intern.str.as_ref().unwrap();
+                if table.get(name).is_some_and(|symbol| symbol.kind == SymbolKind::Constant) {
+                    errors.push(SemaError::new(SemaErrorKind::ConstantExported, format!("Constant {} cannot be exported.", name).as_str(), node.label_intern.span).with_note("Only labels and reservations have an address to export."));
+                }
+            },
+            Statement::Macro(node) => {
+                for arg in &node.args {
+                    if let MacroArg::Label(label) = arg {
+                        check_access(&table, label, &mut errors);
+                    }
+                }
+            },
+            Statement::Instruction(node) => {
+                for arg in &node.args {
+                    if let InstructionArg::Label(label) = arg {
+                        check_access(&table, label, &mut errors);
+                    }
+                }
+            },
+            _ => {}
+        }
+
+        for expression in stmt.expressions() {
+            for label in expression.labels() {
+                check_access(&table, label, &mut errors);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(table)
+    } else {
+        Err(errors)
+    }
+}
+
+
+fn define(table: &mut SymbolTable, name: String, kind: SymbolKind, span: Span, errors: &mut Vec<SemaError>) {
+    if let Some(previous) = table.get(&name) {
+        let desc = if kind == SymbolKind::Constant { "Constant" } else { "Label" };
+        let previous_desc = if previous.kind == SymbolKind::Constant { " as a constant" } else { "" };
+
+        errors.push(SemaError::new(SemaErrorKind::Redefinition, format!("{} {} redefined, previously defined{} on line {}.", desc, name, previous_desc, previous.span.line).as_str(), span));
+        return;
+    }
+
+    table.symbols.insert(name, Symbol { kind, span });
+}
+
+
+fn check_access(table: &SymbolTable, label: &LabelAccess, errors: &mut Vec<SemaError>) {
+    let name = label.str.as_ref().unwrap();
+
+    if table.get(name).is_none() {
+        errors.push(SemaError::new(SemaErrorKind::UndefinedLabel, format!("Undefined label {}.", name).as_str(), label.span).with_help("Define the label or bring it in with an .import directive."));
+    }
+}
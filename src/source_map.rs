@@ -0,0 +1,39 @@
+use crate::span::Span;
+
+
+
+#[derive(Debug)]
+pub struct SourceFile {
+    pub name: String,
+    pub source: String,
+    pub included_from: Option<Span>
+}
+
+#[derive(Debug)]
+#[derive(Default)]
+pub struct SourceMap {
+    pub files: Vec<SourceFile>
+}
+
+impl SourceMap {
+    pub fn add(&mut self, name: &str, source: String, included_from: Option<Span>) -> usize {
+        self.files.push(SourceFile { name: name.to_string(), source, included_from });
+        self.files.len() - 1
+    }
+
+    pub fn file(&self, file: usize) -> &SourceFile {
+        &self.files[file]
+    }
+
+    pub fn include_chain(&self, file: usize) -> Vec<(&str, u32)> {
+        let mut chain = Vec::new();
+        let mut current = self.file(file);
+
+        while let Some(span) = current.included_from {
+            current = self.file(span.file);
+            chain.push((current.name.as_str(), span.line));
+        }
+
+        chain
+    }
+}
@@ -0,0 +1,28 @@
+
+
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+pub struct Span {
+    pub file: usize,
+    pub start: usize,
+    pub end: usize,
+    pub line: u32,
+    pub column: u32
+}
+
+impl Span {
+    pub fn new(file: usize, start: usize, end: usize, line: u32, column: u32) -> Self {
+        Span { file, start, end, line, column }
+    }
+
+    pub fn to(&self, other: &Span) -> Span {
+        Span { file: self.file, start: self.start, end: other.end.max(self.start), line: self.line, column: self.column }
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}